@@ -0,0 +1,1348 @@
+// end-to-end tests of the escrow, paying out in the erc20 stablecoin through the voting contract as
+// the arbiter provider. they run against a local contracts node, see the README.
+#![cfg(feature = "e2e-tests")]
+
+use erc20::Erc20Ref;
+use escrow::EscrowRef;
+use ink::primitives::AccountId;
+use ink_e2e::build_message;
+use shared::cid::CidError;
+use shared::findings::FindingsSummary;
+use shared::time::{Deadline, Duration};
+use voting::VotingRef;
+
+type C = ink_e2e::PolkadotConfig;
+type E = ink::env::DefaultEnvironment;
+type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const REPORT: &str = "QmY7Yh4UquoXHLPFo2XbhXkhBvFoPwmQUSa92pxnxjQuPU";
+const REWORKED: &str = "bafkreibm6jg3ux5qumhcn2b3flc3tyu6dmlb4xa7u5bf44yegnrjhc4yeq";
+const EVIDENCE: &str = "bafybeierhgbz4zp2x2u67urqrgfnrnlukciupzenpqpipiz5nwtq7uxpx4";
+// what bob, charlie, dave and eve start with, and alice keeps, of the stablecoin.
+const FUNDS: u128 = 100_000;
+
+// sends the message to the escrow once a dry run of it succeeded, returning what the dry run
+// returned. a message that fails reverts, so its error only shows in the dry run.
+macro_rules! send {
+    ($client:expr, $signer:expr, $escrow:expr, $message:expr) => {{
+        let message = build_message::<EscrowRef>($escrow).call($message);
+        let outcome = $client
+            .call_dry_run($signer, &message, 0, None)
+            .await
+            .return_value();
+        if outcome.is_ok() {
+            $client
+                .call($signer, message, 0, None)
+                .await
+                .expect("call failed");
+        }
+        outcome
+    }};
+}
+
+macro_rules! query {
+    ($client:expr, $escrow:expr, $message:expr) => {{
+        let message = build_message::<EscrowRef>($escrow).call($message);
+        $client
+            .call_dry_run(&ink_e2e::alice(), &message, 0, None)
+            .await
+            .return_value()
+    }};
+}
+
+// the escrow holding the stablecoin, with alice as its admin, and a voting contract as the
+// arbiter provider, whose admin ferdie takes the provider's share of each audit.
+struct Deployment {
+    token: AccountId,
+    escrow: AccountId,
+    provider: AccountId,
+}
+
+// deploys the contracts, handing out FUNDS of the stablecoin to bob, charlie, dave and eve.
+// alice, bob and charlie approve the escrow for all of theirs.
+async fn deploy(client: &mut ink_e2e::Client<C, E>) -> Deployment {
+    let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+    let ferdie = ink_e2e::account_id(ink_e2e::AccountKeyring::Ferdie);
+    let token = client
+        .instantiate(
+            "erc20",
+            &ink_e2e::alice(),
+            Erc20Ref::new(5 * FUNDS),
+            0,
+            None,
+        )
+        .await
+        .expect("instantiate erc20 failed")
+        .account_id;
+    for keyring in [
+        ink_e2e::AccountKeyring::Bob,
+        ink_e2e::AccountKeyring::Charlie,
+        ink_e2e::AccountKeyring::Dave,
+        ink_e2e::AccountKeyring::Eve,
+    ] {
+        let transfer = build_message::<Erc20Ref>(token)
+            .call(|erc20| erc20.transfer(ink_e2e::account_id(keyring), FUNDS));
+        client
+            .call(&ink_e2e::alice(), transfer, 0, None)
+            .await
+            .expect("transfer failed");
+    }
+    let escrow = client
+        .instantiate(
+            "escrow",
+            &ink_e2e::alice(),
+            EscrowRef::new(token, alice),
+            0,
+            None,
+        )
+        .await
+        .expect("instantiate escrow failed")
+        .account_id;
+    let provider = client
+        .instantiate(
+            "voting",
+            &ink_e2e::alice(),
+            VotingRef::new(escrow, token, ferdie),
+            0,
+            None,
+        )
+        .await
+        .expect("instantiate voting failed")
+        .account_id;
+    for signer in [ink_e2e::alice(), ink_e2e::bob(), ink_e2e::charlie()] {
+        approve(client, &signer, token, escrow, FUNDS).await;
+    }
+    Deployment {
+        token,
+        escrow,
+        provider,
+    }
+}
+
+async fn approve(
+    client: &mut ink_e2e::Client<C, E>,
+    signer: &ink_e2e::Keypair,
+    token: AccountId,
+    spender: AccountId,
+    amount: u128,
+) {
+    let approve = build_message::<Erc20Ref>(token).call(|erc20| erc20.approve(spender, amount));
+    client
+        .call(signer, approve, 0, None)
+        .await
+        .expect("approve failed");
+}
+
+async fn balance_of(
+    client: &mut ink_e2e::Client<C, E>,
+    token: AccountId,
+    owner: AccountId,
+) -> u128 {
+    let balance_of = build_message::<Erc20Ref>(token).call(|erc20| erc20.balance_of(owner));
+    client
+        .call_dry_run(&ink_e2e::alice(), &balance_of, 0, None)
+        .await
+        .return_value()
+}
+
+// bob's audit worth value, assigned to charlie for a week, who submitted REPORT. returns its id.
+async fn submitted_audit(
+    client: &mut ink_e2e::Client<C, E>,
+    d: &Deployment,
+    value: u128,
+    holdback: Option<escrow::HoldbackTerms>,
+) -> u32 {
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let week = Duration::from_days(7);
+    let id = query!(client, d.escrow, |escrow| escrow.get_current_audit_id());
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_new_payment(value, d.provider, week, 1, holdback.clone())
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.assign_audit(id, charlie, value, week)
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.mark_submitted(id, String::from(REPORT), FindingsSummary::default(), None)
+    })
+    .is_ok());
+    id
+}
+
+// waits until a deadline that far from now has passed on chain.
+async fn wait(duration: Duration) {
+    ink_e2e::tokio::time::sleep(std::time::Duration::from_millis(
+        duration.as_millis() + 2_000,
+    ))
+    .await;
+}
+
+#[ink_e2e::test]
+async fn e2e_value_is_only_locked_once_paid_in(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let dave = ink_e2e::account_id(ink_e2e::AccountKeyring::Dave);
+    let week = Duration::from_days(7);
+    // dave holds the tokens, but hasn't approved the escrow for them.
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| {
+        escrow.create_new_payment(1000, d.provider, week, 1, None)
+    });
+    assert!(matches!(x, Err(escrow::Error::InsufficientBalance)));
+    approve(&mut client, &ink_e2e::dave(), d.token, d.escrow, 500).await;
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| {
+        escrow.create_new_payment(1000, d.provider, week, 1, None)
+    });
+    assert!(matches!(x, Err(escrow::Error::InsufficientBalance)));
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_current_audit_id()),
+        0
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        0
+    );
+    assert_eq!(balance_of(&mut client, d.token, dave).await, FUNDS);
+
+    assert!(send!(client, &ink_e2e::dave(), d.escrow, |escrow| {
+        escrow.create_new_payment(500, d.provider, week, 1, None)
+    })
+    .is_ok());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        500
+    );
+    assert_eq!(balance_of(&mut client, d.token, dave).await, FUNDS - 500);
+    // the allowance is used up, so a round dave opens takes nothing more from them.
+    assert!(send!(client, &ink_e2e::dave(), d.escrow, |escrow| {
+        escrow.open_funding_round(1000, d.provider, week, week, 2, None)
+    })
+    .is_ok());
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .contribute(1, 100));
+    assert!(matches!(x, Err(escrow::Error::InsufficientBalance)));
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_sponsor_count(1)),
+        0
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        500
+    );
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_accepted_audit_pays_the_auditor_and_the_provider(
+    mut client: ink_e2e::Client<C, E>,
+) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let ferdie = ink_e2e::account_id(ink_e2e::AccountKeyring::Ferdie);
+    let week = Duration::from_days(7);
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_new_payment(1000, d.provider, week, 1, None)
+    })
+    .is_ok());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        1000
+    );
+    assert_eq!(balance_of(&mut client, d.token, d.escrow).await, 1000);
+    assert_eq!(balance_of(&mut client, d.token, bob).await, FUNDS - 1000);
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.assign_audit(0, charlie, 1000, week)
+    })
+    .is_ok());
+
+    // every change of status is recorded, but a report that can't be read doesn't change it.
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_audit_history_len(0)),
+        2
+    );
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.mark_submitted(
+            0,
+            String::from("good work there"),
+            FindingsSummary::default(),
+            None,
+        )
+    });
+    assert!(matches!(
+        x,
+        Err(escrow::Error::InvalidCid(CidError::UnsupportedMultibase))
+    ));
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.mark_submitted(0, String::from(REPORT), FindingsSummary::default(), None)
+    })
+    .is_ok());
+    let history = query!(client, d.escrow, |escrow| escrow
+        .get_audit_history(0, 0, 10));
+    assert_eq!(history.len(), 3);
+    assert!(history[0].from.is_none());
+    assert_eq!(history[0].amount, 1000);
+    assert!(matches!(
+        history[2].from,
+        Some(escrow::AuditStatus::AuditAssigned)
+    ));
+    assert!(matches!(history[2].to, escrow::AuditStatus::AuditSubmitted));
+    assert_eq!(history[2].actor, charlie);
+    assert_eq!(history[2].amount, 0);
+
+    // the provider's admin gets 2% and the auditor the rest.
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assess_audit(0, true, 0))
+    .is_ok());
+    assert_eq!(balance_of(&mut client, d.token, charlie).await, FUNDS + 980);
+    assert_eq!(balance_of(&mut client, d.token, ferdie).await, 20);
+    assert_eq!(balance_of(&mut client, d.token, d.escrow).await, 0);
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        0
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_audit_history_len(0)),
+        4
+    );
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_accepted_partial_payout_refunds_the_rest(
+    mut client: ink_e2e::Client<C, E>,
+) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let ferdie = ink_e2e::account_id(ink_e2e::AccountKeyring::Ferdie);
+    let id = submitted_audit(&mut client, &d, 1000, None).await;
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .offer_partial_payout(id, 60));
+    assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .accept_partial_payout(id));
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .offer_partial_payout(id, 100));
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .offer_partial_payout(id, 50))
+    .is_ok());
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .offer_partial_payout(id, 60))
+    .is_ok());
+    let offer = query!(client, d.escrow, |escrow| escrow.query_partial_offer(id));
+    assert_eq!(offer.unwrap().percentage, 60);
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow
+            .allowed_actions(id, charlie))[..],
+        [
+            escrow::AuditAction::AcceptPartialPayout,
+            escrow::AuditAction::RejectPartialPayout
+        ]
+    ));
+
+    // of the 600 agreed on the provider's admin gets 2%, and the patron the 400 left over.
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .accept_partial_payout(id))
+    .is_ok());
+    assert_eq!(balance_of(&mut client, d.token, charlie).await, FUNDS + 588);
+    assert_eq!(balance_of(&mut client, d.token, ferdie).await, 12);
+    assert_eq!(balance_of(&mut client, d.token, bob).await, FUNDS - 600);
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        0
+    );
+    assert!(query!(client, d.escrow, |escrow| escrow.query_partial_offer(id)).is_none());
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow.get_paymentinfo(id))
+            .unwrap()
+            .currentstatus,
+        escrow::AuditStatus::AuditCompleted
+    ));
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_escape_hatch_returns_the_bond_of_a_dispute(
+    mut client: ink_e2e::Client<C, E>,
+) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let id = submitted_audit(&mut client, &d, 1000, None).await;
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.submit_evidence(id, String::from(EVIDENCE), String::from("H-1"))
+    });
+    assert!(matches!(x, Err(escrow::Error::WrongState)));
+
+    // turning the offer down is a dispute, for which the auditor posts 5% of the value.
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .offer_partial_payout(id, 60))
+    .is_ok());
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .reject_partial_payout(id))
+    .is_ok());
+    let bond = query!(client, d.escrow, |escrow| escrow.get_arbitration_bond(id)).unwrap();
+    assert_eq!(bond.poster, charlie);
+    assert_eq!(bond.amount, 50);
+    assert_eq!(balance_of(&mut client, d.token, charlie).await, FUNDS - 50);
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        1050
+    );
+    assert!(query!(client, d.escrow, |escrow| escrow.query_partial_offer(id)).is_none());
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow.get_paymentinfo(id))
+            .unwrap()
+            .currentstatus,
+        escrow::AuditStatus::AuditAwaitingValidation
+    ));
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .offer_partial_payout(id, 60));
+    assert!(matches!(x, Err(escrow::Error::WrongState)));
+
+    // both sides make their case while the audit awaits validation.
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.submit_evidence(id, String::from(EVIDENCE), String::from("H-1"))
+    })
+    .is_ok());
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.submit_evidence(id, String::from(EVIDENCE), "x".repeat(65))
+    });
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.submit_evidence(
+            id,
+            String::from(REWORKED),
+            String::from("H-1 is out of scope"),
+        )
+    })
+    .is_ok());
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| {
+        escrow.submit_evidence(id, String::from(EVIDENCE), String::new())
+    });
+    assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+    let evidence = query!(client, d.escrow, |escrow| escrow.get_evidence(id, 0, 10));
+    assert_eq!(evidence.len(), 2);
+    assert_eq!(evidence[0].submitter, bob);
+    assert_eq!(evidence[1].id, 1);
+    assert_eq!(evidence[1].label, "H-1 is out of scope");
+
+    // without a ruling the value is split, and the bond goes back to the auditor.
+    assert!(send!(client, &ink_e2e::alice(), d.escrow, |escrow| {
+        escrow.set_escape_hatch_delay(Duration::ZERO)
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::alice(), d.escrow, |escrow| escrow.pause()).is_ok());
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .escape_hatch_withdraw(id))
+    .is_ok());
+    assert_eq!(balance_of(&mut client, d.token, charlie).await, FUNDS + 500);
+    assert_eq!(balance_of(&mut client, d.token, bob).await, FUNDS - 500);
+    assert_eq!(balance_of(&mut client, d.token, d.escrow).await, 0);
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        0
+    );
+    assert!(query!(client, d.escrow, |escrow| escrow.get_arbitration_bond(id)).is_none());
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_crowdfunded_audit_refunds_its_sponsors_pro_rata(
+    mut client: ink_e2e::Client<C, E>,
+) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let dave = ink_e2e::account_id(ink_e2e::AccountKeyring::Dave);
+    let eve = ink_e2e::account_id(ink_e2e::AccountKeyring::Eve);
+    let week = Duration::from_days(7);
+    approve(&mut client, &ink_e2e::dave(), d.token, d.escrow, FUNDS).await;
+    approve(&mut client, &ink_e2e::eve(), d.token, d.escrow, FUNDS).await;
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.open_funding_round(1000, d.provider, week, week, 1, None)
+    })
+    .is_ok());
+
+    // only what the round still needs is taken, and reaching the target lists the audit.
+    assert!(send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .contribute(0, 750))
+    .is_ok());
+    assert!(send!(client, &ink_e2e::eve(), d.escrow, |escrow| escrow
+        .contribute(0, 500))
+    .is_ok());
+    assert_eq!(balance_of(&mut client, d.token, eve).await, FUNDS - 250);
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        1000
+    );
+    let round = query!(client, d.escrow, |escrow| escrow.get_paymentinfo(0)).unwrap();
+    assert!(matches!(
+        round.currentstatus,
+        escrow::AuditStatus::AuditCreated
+    ));
+    assert_eq!(round.value, 1000);
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_sponsor_count(0)),
+        2
+    );
+
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.assign_audit(0, charlie, 1000, week)
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.mark_submitted(0, String::from(REPORT), FindingsSummary::default(), None)
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .offer_partial_payout(0, 60))
+    .is_ok());
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .accept_partial_payout(0))
+    .is_ok());
+    // the 400 the steward doesn't pay goes back to the sponsors, 3 to 1.
+    assert_eq!(
+        balance_of(&mut client, d.token, dave).await,
+        FUNDS - 750 + 300
+    );
+    assert_eq!(
+        balance_of(&mut client, d.token, eve).await,
+        FUNDS - 250 + 100
+    );
+    assert_eq!(balance_of(&mut client, d.token, charlie).await, FUNDS + 588);
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        0
+    );
+    let contribution = query!(client, d.escrow, |escrow| escrow.get_contribution(0, dave));
+    assert_eq!(contribution.unwrap().refunded, 300);
+
+    // a round the steward calls off hands every sponsor back what they contributed.
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.open_funding_round(1000, d.provider, week, week, 2, None)
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .contribute(1, 100))
+    .is_ok());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        100
+    );
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .expire_audit(1));
+    assert!(matches!(x, Err(escrow::Error::DeadlineNotPassed)));
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .expire_audit(1))
+    .is_ok());
+    assert_eq!(
+        balance_of(&mut client, d.token, dave).await,
+        FUNDS - 750 + 300
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        0
+    );
+    assert_eq!(balance_of(&mut client, d.token, d.escrow).await, 0);
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_holdback_goes_to_the_auditor_after_its_window(
+    mut client: ink_e2e::Client<C, E>,
+) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let window = Duration::from_secs(60);
+    let terms = escrow::HoldbackTerms {
+        percentage: 20,
+        window,
+    };
+
+    // nothing is held back from the auditor of an audit without holdback terms.
+    let id = submitted_audit(&mut client, &d, 1000, None).await;
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.file_bug_claim(id, String::from(EVIDENCE))
+    });
+    assert!(matches!(x, Err(escrow::Error::WrongState)));
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assess_audit(id, true, 0))
+    .is_ok());
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.file_bug_claim(id, String::from(EVIDENCE))
+    });
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .release_holdback(id));
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    assert_eq!(balance_of(&mut client, d.token, charlie).await, FUNDS + 980);
+
+    // 20% of the auditor's 980 stays in the escrow until the window is over.
+    let id = submitted_audit(&mut client, &d, 1000, Some(terms.clone())).await;
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assess_audit(id, true, 0))
+    .is_ok());
+    assert_eq!(
+        balance_of(&mut client, d.token, charlie).await,
+        FUNDS + 980 + 784
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        196
+    );
+    let holdback = query!(client, d.escrow, |escrow| escrow.get_holdback(id)).unwrap();
+    assert_eq!(holdback.amount, 196);
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .release_holdback(id));
+    assert!(matches!(x, Err(escrow::Error::DeadlineNotPassed)));
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.file_bug_claim(id, String::from(EVIDENCE))
+    });
+    assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+
+    // the patron claims a bug was missed in the next audit, which keeps its holdback past the window.
+    let claimed = submitted_audit(&mut client, &d, 1000, Some(terms)).await;
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assess_audit(claimed, true, 0))
+    .is_ok());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        392
+    );
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.file_bug_claim(claimed, String::from(EVIDENCE))
+    })
+    .is_ok());
+    let claim = query!(client, d.escrow, |escrow| escrow.get_bug_claim(claimed)).unwrap();
+    assert_eq!(claim.status, escrow::BugClaimStatus::Pending);
+    assert_eq!(
+        claim.resolve_by,
+        Deadline::after(claim.filed_at, escrow::BUG_CLAIM_RESOLUTION_WINDOW)
+    );
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.file_bug_claim(claimed, String::from(EVIDENCE))
+    });
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+
+    wait(window).await;
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.file_bug_claim(id, String::from(EVIDENCE))
+    });
+    assert!(matches!(x, Err(escrow::Error::DeadlinePassed)));
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .release_holdback(claimed));
+    assert!(matches!(x, Err(escrow::Error::BugClaimPending)));
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow
+            .allowed_actions(claimed, d.provider))[..],
+        [escrow::AuditAction::ResolveBugClaim]
+    ));
+    assert!(send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .release_holdback(id))
+    .is_ok());
+    assert_eq!(
+        balance_of(&mut client, d.token, charlie).await,
+        FUNDS + 2 * 980 + 784
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        196
+    );
+    assert!(query!(client, d.escrow, |escrow| escrow.get_holdback(id)).is_none());
+
+    // nor is it stuck while the escrow is paused, the claim still pending splitting it equally.
+    assert!(send!(client, &ink_e2e::alice(), d.escrow, |escrow| {
+        escrow.set_escape_hatch_delay(Duration::ZERO)
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::alice(), d.escrow, |escrow| escrow.pause()).is_ok());
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow
+            .allowed_actions(claimed, charlie))[..],
+        [escrow::AuditAction::EscapeHatch]
+    ));
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .escape_hatch_withdraw(claimed))
+    .is_ok());
+    assert_eq!(
+        balance_of(&mut client, d.token, charlie).await,
+        FUNDS + 2 * 980 + 784 + 98
+    );
+    assert_eq!(
+        balance_of(&mut client, d.token, bob).await,
+        FUNDS - 3000 + 98
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        0
+    );
+    assert_eq!(balance_of(&mut client, d.token, d.escrow).await, 0);
+    let claim = query!(client, d.escrow, |escrow| escrow.get_bug_claim(claimed)).unwrap();
+    assert_eq!(claim.status, escrow::BugClaimStatus::Lapsed);
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_keepers_expire_abandoned_audits_for_a_fee(
+    mut client: ink_e2e::Client<C, E>,
+) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let dave = ink_e2e::account_id(ink_e2e::AccountKeyring::Dave);
+    let deadline = Duration::from_secs(60);
+    let week = Duration::from_days(7);
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_new_payment(1000, d.provider, week, 1, None)
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_new_payment(1000, d.provider, week, 2, None)
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.assign_audit(1, charlie, 1000, deadline)
+    })
+    .is_ok());
+    let submitted = submitted_audit(&mut client, &d, 1000, None).await;
+    assert!(send!(client, &ink_e2e::alice(), d.escrow, |escrow| escrow
+        .set_keeper_fee(d.token, 10))
+    .is_ok());
+
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .expire_audit(0));
+    assert!(matches!(x, Err(escrow::Error::DeadlineNotPassed)));
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .expire_audit(1));
+    assert!(matches!(x, Err(escrow::Error::DeadlineNotPassed)));
+    let expired = send!(client, &ink_e2e::dave(), d.escrow, |escrow| {
+        escrow.batch_expire(vec![0, 1, submitted, 7])
+    });
+    assert!(matches!(expired, Ok(ids) if ids.is_empty()));
+
+    // past the assigned audit's deadline anyone can expire it, but the created one
+    // only becomes fair game once the listing timeout is over.
+    wait(deadline).await;
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow.allowed_actions(1, dave))[..],
+        [escrow::AuditAction::Expire]
+    ));
+    assert!(query!(client, d.escrow, |escrow| escrow.allowed_actions(0, dave)).is_empty());
+    assert!(query!(client, d.escrow, |escrow| escrow
+        .allowed_actions(submitted, dave))
+    .is_empty());
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .expire_audit(1));
+    assert!(matches!(x, Err(escrow::Error::KeeperFundEmpty)));
+
+    // the fee comes out of what the fee manager paid into the keeper fund.
+    assert!(send!(client, &ink_e2e::alice(), d.escrow, |escrow| escrow
+        .fund_keeper_fees(d.token, 10))
+    .is_ok());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_keeper_fund(d.token)),
+        10
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        3010
+    );
+    assert!(send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .expire_audit(1))
+    .is_ok());
+    assert_eq!(balance_of(&mut client, d.token, dave).await, FUNDS + 10);
+    assert_eq!(balance_of(&mut client, d.token, bob).await, FUNDS - 2000);
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_keeper_fund(d.token)),
+        0
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        2000
+    );
+
+    assert!(send!(client, &ink_e2e::alice(), d.escrow, |escrow| {
+        escrow.set_listing_timeout(Duration::ZERO)
+    })
+    .is_ok());
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow.allowed_actions(0, dave))[..],
+        [escrow::AuditAction::Expire]
+    ));
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .expire_audit(0));
+    assert!(matches!(x, Err(escrow::Error::KeeperFundEmpty)));
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_extension_requests_lapse_with_the_deadline(
+    mut client: ink_e2e::Client<C, E>,
+) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let dave = ink_e2e::account_id(ink_e2e::AccountKeyring::Dave);
+    let deadline = Duration::from_secs(60);
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_new_payment(1000, d.provider, deadline, 1, None)
+    })
+    .is_ok());
+    // the patron stands in for the auditor of a created audit, which has no deadline to extend.
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.request_additional_time(0, Duration::from_millis(20), 5)
+    });
+    assert!(matches!(x, Err(escrow::Error::WrongState)));
+
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.assign_audit(0, charlie, 1000, deadline)
+    })
+    .is_ok());
+    assert!(query!(client, d.escrow, |escrow| escrow.allowed_actions(0, bob)).is_empty());
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow
+            .allowed_actions(0, charlie))[..],
+        [
+            escrow::AuditAction::RequestAdditionalTime,
+            escrow::AuditAction::Submit,
+            escrow::AuditAction::Resign
+        ]
+    ));
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.request_additional_time(0, Duration::from_millis(20), 5)
+    })
+    .is_ok());
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow.allowed_actions(0, bob))[..],
+        [
+            escrow::AuditAction::CounterAdditionalTime,
+            escrow::AuditAction::ApproveAdditionalTime,
+            escrow::AuditAction::RejectAdditionalTime
+        ]
+    ));
+    assert!(query!(client, d.escrow, |escrow| escrow.allowed_actions(0, dave)).is_empty());
+
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.counter_additional_time(0, Duration::from_millis(15), 10)
+    })
+    .is_ok());
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .approve_additional_time(0));
+    assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .reject_additional_time(0))
+    .is_ok());
+    assert!(query!(client, d.escrow, |escrow| escrow
+        .query_timeincreaserequest(0))
+    .is_none());
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.request_additional_time(0, Duration::from_days(7), 5)
+    })
+    .is_ok());
+
+    let requests = query!(client, d.escrow, |escrow| escrow
+        .get_extension_requests(0, 0, 10));
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].status, escrow::ExtensionStatus::Superseded);
+    assert_eq!(requests[1].proposer, bob);
+    assert_eq!(requests[1].status, escrow::ExtensionStatus::Rejected);
+    assert_eq!(requests[2].status, escrow::ExtensionStatus::Pending);
+    // a 7 day extension is 7 days of block time.
+    assert_eq!(requests[2].extension.as_millis(), 7 * 24 * 60 * 60 * 1000);
+
+    // the request lapses with the deadline it would have extended.
+    wait(deadline).await;
+    assert!(query!(client, d.escrow, |escrow| escrow
+        .query_timeincreaserequest(0))
+    .is_none());
+    let requests = query!(client, d.escrow, |escrow| escrow
+        .get_extension_requests(0, 2, 1));
+    assert_eq!(requests[0].status, escrow::ExtensionStatus::Expired);
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_resigned_audit_is_listed_again(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let dave = ink_e2e::account_id(ink_e2e::AccountKeyring::Dave);
+    let day = Duration::from_days(1);
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_new_payment(1000, d.provider, day, 1, None)
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assign_audit(0, charlie, 1000, day))
+    .is_ok());
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .resign(0, None, 0));
+    assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.request_additional_time(0, day, 5)
+    })
+    .is_ok());
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .resign(0, None, 1000));
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.resign(0, Some(String::from(REPORT)), 300)
+    })
+    .is_ok());
+
+    let payment_info = query!(client, d.escrow, |escrow| escrow.get_paymentinfo(0)).unwrap();
+    assert!(matches!(
+        payment_info.currentstatus,
+        escrow::AuditStatus::AuditCreated
+    ));
+    assert_eq!(payment_info.auditor, bob);
+    assert_eq!(payment_info.value, 1000);
+    assert_eq!(payment_info.duration, day);
+    assert_eq!(payment_info.deadline, Deadline::default());
+    assert!(query!(client, d.escrow, |escrow| escrow
+        .query_timeincreaserequest(0))
+    .is_none());
+    let resignation = query!(client, d.escrow, |escrow| escrow
+        .query_pending_resignation(0))
+    .unwrap();
+    assert_eq!(resignation.auditor, charlie);
+    assert_eq!(resignation.payout, 300);
+
+    // the patron answers the payout before the audit can expire or go to someone else.
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .expire_audit(0));
+    assert!(matches!(x, Err(escrow::Error::ResignationPending)));
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow.allowed_actions(0, bob))[..],
+        [
+            escrow::AuditAction::ApproveResignationPayout,
+            escrow::AuditAction::DeclineResignationPayout
+        ]
+    ));
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assign_audit(0, dave, 1000, day));
+    assert!(matches!(x, Err(escrow::Error::ResignationPending)));
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .decline_resignation_payout(0))
+    .is_ok());
+    let resignations = query!(client, d.escrow, |escrow| escrow.get_resignations(0, 0, 1));
+    assert_eq!(resignations[0].status, escrow::ResignationStatus::Declined);
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .decline_resignation_payout(0));
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assign_audit(0, dave, 1000, day))
+    .is_ok());
+    let payment_info = query!(client, d.escrow, |escrow| escrow.get_paymentinfo(0)).unwrap();
+    assert_eq!(payment_info.auditor, dave);
+    assert_eq!(
+        payment_info.deadline,
+        Deadline::after(payment_info.starttime, day)
+    );
+    assert_eq!(balance_of(&mut client, d.token, charlie).await, FUNDS);
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        1000
+    );
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_sealed_report_pays_only_for_its_key(
+    mut client: ink_e2e::Client<C, E>,
+) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let week = Duration::from_days(7);
+    let key = [7u8; 32];
+    let mut commitment = [0u8; 32];
+    ink::env::hash_bytes::<ink::env::hash::Blake2x256>(&key, &mut commitment);
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_new_payment(1000, d.provider, week, 1, None)
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assign_audit(0, charlie, 1000, week))
+    .is_ok());
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.mark_submitted_sealed(
+            0,
+            String::from(REPORT),
+            commitment.into(),
+            FindingsSummary::default(),
+            None,
+        )
+    })
+    .is_ok());
+    let report = query!(client, d.escrow, |escrow| escrow.get_report(0, 0)).unwrap();
+    assert_eq!(report.key_commitment, Some(commitment.into()));
+    assert!(report.unlocks_with(&key));
+    assert!(!report.unlocks_with(&[8u8; 32]));
+    // nothing to reveal before the patron accepts.
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .reveal_report_key(0, key));
+    assert!(matches!(x, Err(escrow::Error::WrongState)));
+    assert!(query!(client, d.escrow, |escrow| escrow.get_report_key(0, 0)).is_none());
+
+    // an accepted sealed report waits for its key, which only its auditor can reveal.
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assess_audit(0, true, 0))
+    .is_ok());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_sealed_payout(0)),
+        980
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        980
+    );
+    assert_eq!(balance_of(&mut client, d.token, charlie).await, FUNDS);
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow
+            .allowed_actions(0, charlie))[..],
+        [escrow::AuditAction::RevealKey]
+    ));
+    assert!(query!(client, d.escrow, |escrow| escrow.allowed_actions(0, bob)).is_empty());
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .reveal_report_key(0, key));
+    assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+    // the payout can't go back to the patron while the auditor still has time to reveal.
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .expire_audit(0));
+    assert!(matches!(x, Err(escrow::Error::DeadlineNotPassed)));
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .reveal_report_key(0, [8u8; 32]));
+    assert!(matches!(x, Err(escrow::Error::KeyMismatch)));
+
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .reveal_report_key(0, key))
+    .is_ok());
+    assert_eq!(balance_of(&mut client, d.token, charlie).await, FUNDS + 980);
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        0
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_sealed_payout(0)),
+        0
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_report_key(0, 0)),
+        Some(key)
+    );
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_report_key_shared_with_registered_readers(
+    mut client: ink_e2e::Client<C, E>,
+) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let dave = ink_e2e::account_id(ink_e2e::AccountKeyring::Dave);
+    let eve = ink_e2e::account_id(ink_e2e::AccountKeyring::Eve);
+    let week = Duration::from_days(7);
+    let wrapped_for = |recipient: AccountId, key: u8| shared::keys::WrappedKey {
+        recipient,
+        recipient_key: [key; 32],
+        ephemeral_key: [9; 32],
+        sealed: vec![0; shared::keys::WRAPPED_KEY_LEN],
+    };
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_new_payment(1000, d.provider, week, 1, None)
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assign_audit(0, charlie, 1000, week))
+    .is_ok());
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .register_public_key([1; 32]))
+    .is_ok());
+    assert!(send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .register_public_key([3; 32]))
+    .is_ok());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_public_key(dave)),
+        Some([3; 32])
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_public_key(eve)),
+        None
+    );
+
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.share_report_key(0, 0, vec![wrapped_for(bob, 1)])
+    });
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.mark_submitted(0, String::from(REPORT), FindingsSummary::default(), None)
+    })
+    .is_ok());
+    // a key wrapped to anything but the recipient's registered key is of no use to them.
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.share_report_key(0, 0, vec![wrapped_for(bob, 2)])
+    });
+    assert!(matches!(x, Err(escrow::Error::UnknownPublicKey)));
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.share_report_key(0, 0, vec![wrapped_for(eve, 4)])
+    });
+    assert!(matches!(x, Err(escrow::Error::UnknownPublicKey)));
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.share_report_key(0, 0, vec![wrapped_for(bob, 1)])
+    })
+    .is_ok());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_wrapped_key(0, 0, bob)),
+        Some(wrapped_for(bob, 1))
+    );
+
+    // only those who can read the report pass it on.
+    let x = send!(client, &ink_e2e::dave(), d.escrow, |escrow| {
+        escrow.share_report_key(0, 0, vec![wrapped_for(dave, 3)])
+    });
+    assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.share_report_key(0, 0, vec![wrapped_for(dave, 3)])
+    })
+    .is_ok());
+    assert!(send!(client, &ink_e2e::dave(), d.escrow, |escrow| escrow
+        .share_report_key(0, 0, Vec::new()))
+    .is_ok());
+    assert!(query!(client, d.escrow, |escrow| escrow
+        .get_wrapped_key(0, 1, dave))
+    .is_none());
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_remediation_of_a_completed_audit_is_offered_to_its_auditor(
+    mut client: ink_e2e::Client<C, E>,
+) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let dave = ink_e2e::account_id(ink_e2e::AccountKeyring::Dave);
+    let week = Duration::from_days(7);
+    let id = submitted_audit(&mut client, &d, 1000, None).await;
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.request_remediation(id, 500, week, 1, None)
+    });
+    assert!(matches!(x, Err(escrow::Error::WrongState)));
+
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assess_audit(id, true, 0))
+    .is_ok());
+    assert!(matches!(
+        query!(client, d.escrow, |escrow| escrow.allowed_actions(id, bob))[..],
+        [
+            escrow::AuditAction::RequestRemediation,
+            escrow::AuditAction::CreateReaudit
+        ]
+    ));
+    assert!(query!(client, d.escrow, |escrow| escrow
+        .allowed_actions(id, charlie))
+    .is_empty());
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.request_remediation(id, 500, week, 1, None)
+    });
+    assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.request_remediation(id, 0, week, 1, None)
+    });
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.request_remediation(id, 500, Duration::ZERO, 1, None)
+    });
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_remediation(id)),
+        None
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_current_audit_id()),
+        1
+    );
+
+    // the fix review waits for the auditor of the completed audit to take it on.
+    let terms = escrow::HoldbackTerms {
+        percentage: 20,
+        window: Duration::from_days(30),
+    };
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.request_remediation(id, 500, week, 1, Some(terms.clone()))
+    })
+    .is_ok());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_holdback_terms(1))
+            .unwrap()
+            .percentage,
+        20
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_remediation(id)),
+        Some(1)
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_remediated_audit(1)),
+        Some(id)
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        500
+    );
+    assert_eq!(balance_of(&mut client, d.token, bob).await, FUNDS - 1500);
+    let remediation = query!(client, d.escrow, |escrow| escrow.get_paymentinfo(1)).unwrap();
+    assert!(matches!(
+        remediation.currentstatus,
+        escrow::AuditStatus::AuditCreated
+    ));
+    assert_eq!(remediation.arbiterprovider, d.provider);
+    assert_eq!(remediation.deadline, Deadline::default());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_first_refusal(1))
+            .unwrap()
+            .auditor,
+        charlie
+    );
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.request_remediation(id, 500, week, 1, None)
+    });
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assign_audit(1, dave, 500, week));
+    assert!(matches!(x, Err(escrow::Error::FirstRefusalPending)));
+    assert!(send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .accept_first_refusal(1))
+    .is_ok());
+    let remediation = query!(client, d.escrow, |escrow| escrow.get_paymentinfo(1)).unwrap();
+    assert!(matches!(
+        remediation.currentstatus,
+        escrow::AuditStatus::AuditAssigned
+    ));
+    assert_eq!(remediation.auditor, charlie);
+    assert_eq!(
+        remediation.deadline,
+        Deadline::after(remediation.starttime, week)
+    );
+    Ok(())
+}
+
+#[ink_e2e::test]
+async fn e2e_reaudit_follows_a_completed_audit(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+    let d = deploy(&mut client).await;
+    let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+    let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+    let week = Duration::from_days(7);
+    let id = submitted_audit(&mut client, &d, 1000, None).await;
+    let scope = escrow::AuditScope {
+        repo: ink::primitives::Hash::from([1; 32]),
+        commit: vec![2; 20],
+        previous_report_version: Some(0),
+    };
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_reaudit(id, scope.clone(), 1000, d.provider, week, 1, true, None)
+    });
+    assert!(matches!(x, Err(escrow::Error::WrongState)));
+
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| escrow
+        .assess_audit(id, true, 0))
+    .is_ok());
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| {
+        escrow.create_reaudit(id, scope.clone(), 1000, d.provider, week, 1, true, None)
+    });
+    assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+    // the earlier audit only has the one report.
+    let mut later_version = scope.clone();
+    later_version.previous_report_version = Some(1);
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_reaudit(
+            id,
+            later_version.clone(),
+            1000,
+            d.provider,
+            week,
+            1,
+            true,
+            None,
+        )
+    });
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    let mut no_commit = scope.clone();
+    no_commit.commit = Vec::new();
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_reaudit(id, no_commit.clone(), 1000, d.provider, week, 1, true, None)
+    });
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+
+    assert!(query!(client, d.escrow, |escrow| escrow.get_ancestors(id)).is_empty());
+    assert!(query!(client, d.escrow, |escrow| escrow.get_reaudits(id, 0, 10)).is_empty());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_parent(id)),
+        None
+    );
+    assert!(query!(client, d.escrow, |escrow| escrow.get_first_refusal(id)).is_none());
+    let x = send!(client, &ink_e2e::charlie(), d.escrow, |escrow| escrow
+        .accept_first_refusal(id));
+    assert!(matches!(x, Err(escrow::Error::UnAuthorisedCall)));
+
+    // the re-audit can hold back part of its own auditor's payout.
+    let mut terms = escrow::HoldbackTerms {
+        percentage: escrow::MAX_HOLDBACK_PERCENTAGE + 1,
+        window: week,
+    };
+    let x = send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_reaudit(
+            id,
+            scope.clone(),
+            1000,
+            d.provider,
+            week,
+            1,
+            true,
+            Some(terms.clone()),
+        )
+    });
+    assert!(matches!(x, Err(escrow::Error::InvalidArgument)));
+    terms.percentage = 10;
+    assert!(send!(client, &ink_e2e::bob(), d.escrow, |escrow| {
+        escrow.create_reaudit(
+            id,
+            scope.clone(),
+            1000,
+            d.provider,
+            week,
+            1,
+            true,
+            Some(terms.clone()),
+        )
+    })
+    .is_ok());
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_parent(1)),
+        Some(id)
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_holdback_terms(1))
+            .unwrap()
+            .percentage,
+        10
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_first_refusal(1))
+            .unwrap()
+            .auditor,
+        charlie
+    );
+    assert_eq!(
+        query!(client, d.escrow, |escrow| escrow.get_total_locked(d.token)),
+        1000
+    );
+    assert_eq!(balance_of(&mut client, d.token, bob).await, FUNDS - 2000);
+    Ok(())
+}
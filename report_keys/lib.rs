@@ -0,0 +1,183 @@
+//! Off-chain helpers for reports on private codebases.
+//!
+//! The auditor encrypts the report with a random content key, and wraps that key with [`wrap`]
+//! for the patron and the arbiters, to the x25519 public keys they registered on escrow. Each of
+//! them gets the content key back with [`unwrap`] and their secret key, which never leaves them.
+//! A sealed report is revealed with its content key too, so [`commitment`] gives what escrow
+//! checks the revealed key against.
+//!
+//! The content key is wrapped with ChaCha20-Poly1305, under a key derived with HKDF-SHA256 from
+//! the x25519 agreement between a one-off ephemeral key and the recipient's key. The recipient's
+//! account is authenticated along with it, so a wrapped key can't be passed off as someone else's.
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use hkdf::Hkdf;
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha256;
+use shared::keys::{PublicKey, WrappedKey};
+use shared::AccountId;
+
+pub use x25519_dalek::StaticSecret;
+
+/// the symmetric key a report is encrypted with.
+pub type ContentKey = [u8; 32];
+
+const WRAP_INFO: &[u8] = b"report_keys wrap v1";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// the content key was wrapped to another public key than the secret key's.
+    WrongRecipient,
+    /// the wrapped key doesn't open, it was altered or isn't for this account.
+    Corrupted,
+}
+
+/// a new key pair, whose public key is registered on escrow while the secret key stays with its owner.
+pub fn generate_keypair<R: RngCore + CryptoRng>(rng: &mut R) -> (StaticSecret, PublicKey) {
+    let secret = StaticSecret::random_from_rng(rng);
+    let public = public_key(&secret);
+    (secret, public)
+}
+
+pub fn public_key(secret: &StaticSecret) -> PublicKey {
+    x25519_dalek::PublicKey::from(secret).to_bytes()
+}
+
+pub fn generate_content_key<R: RngCore + CryptoRng>(rng: &mut R) -> ContentKey {
+    let mut key = ContentKey::default();
+    rng.fill_bytes(&mut key);
+    key
+}
+
+/// the commitment escrow expects with a sealed report, the BLAKE2b-256 hash of its content key.
+pub fn commitment(key: &ContentKey) -> [u8; 32] {
+    Blake2b::<U32>::digest(key).into()
+}
+
+/// wraps the content key for the recipient, to the public key they registered.
+pub fn wrap<R: RngCore + CryptoRng>(
+    key: &ContentKey,
+    recipient: AccountId,
+    recipient_key: &PublicKey,
+    rng: &mut R,
+) -> WrappedKey {
+    let ephemeral = StaticSecret::random_from_rng(rng);
+    let ephemeral_key = public_key(&ephemeral);
+    let agreed = ephemeral.diffie_hellman(&x25519_dalek::PublicKey::from(*recipient_key));
+    let payload = Payload {
+        msg: key,
+        aad: recipient.as_ref(),
+    };
+    let sealed = cipher(agreed.as_bytes(), &ephemeral_key, recipient_key)
+        .encrypt(&Nonce::default(), payload)
+        .expect("sealing a content key can't fail");
+    WrappedKey {
+        recipient,
+        recipient_key: *recipient_key,
+        ephemeral_key,
+        sealed,
+    }
+}
+
+/// unwraps a content key wrapped for the holder of the secret key.
+pub fn unwrap(wrapped: &WrappedKey, secret: &StaticSecret) -> Result<ContentKey, Error> {
+    if public_key(secret) != wrapped.recipient_key {
+        return Err(Error::WrongRecipient);
+    }
+    let agreed = secret.diffie_hellman(&x25519_dalek::PublicKey::from(wrapped.ephemeral_key));
+    // an ephemeral key of low order agrees on the same secret with everyone.
+    if !agreed.was_contributory() {
+        return Err(Error::Corrupted);
+    }
+    let payload = Payload {
+        msg: &wrapped.sealed,
+        aad: wrapped.recipient.as_ref(),
+    };
+    let key = cipher(
+        agreed.as_bytes(),
+        &wrapped.ephemeral_key,
+        &wrapped.recipient_key,
+    )
+    .decrypt(&Nonce::default(), payload)
+    .map_err(|_| Error::Corrupted)?;
+    ContentKey::try_from(key).map_err(|_| Error::Corrupted)
+}
+
+// every wrap agrees on a new key, so the nonce never needs to change.
+fn cipher(
+    agreed: &[u8; 32],
+    ephemeral_key: &PublicKey,
+    recipient_key: &PublicKey,
+) -> ChaCha20Poly1305 {
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(ephemeral_key);
+    salt[32..].copy_from_slice(recipient_key);
+    let mut wrapping_key = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(&salt), agreed)
+        .expand(WRAP_INFO, &mut wrapping_key)
+        .expect("32 bytes is a valid length");
+    ChaCha20Poly1305::new(&wrapping_key.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    const AUDITOR: [u8; 32] = [1; 32];
+    const ARBITER: [u8; 32] = [2; 32];
+
+    #[test]
+    fn recipient_unwraps_the_content_key() {
+        let (secret, public) = generate_keypair(&mut OsRng);
+        let key = generate_content_key(&mut OsRng);
+        let wrapped = wrap(&key, ARBITER.into(), &public, &mut OsRng);
+        assert_eq!(wrapped.recipient_key, public);
+        assert_eq!(unwrap(&wrapped, &secret), Ok(key));
+        // wrapping again gives another wrapped key for the same content key.
+        let again = wrap(&key, ARBITER.into(), &public, &mut OsRng);
+        assert_ne!(again.sealed, wrapped.sealed);
+        assert_eq!(unwrap(&again, &secret), Ok(key));
+    }
+
+    #[test]
+    fn nobody_else_unwraps_it() {
+        let (_, public) = generate_keypair(&mut OsRng);
+        let (other, _) = generate_keypair(&mut OsRng);
+        let key = generate_content_key(&mut OsRng);
+        let wrapped = wrap(&key, ARBITER.into(), &public, &mut OsRng);
+        assert_eq!(unwrap(&wrapped, &other), Err(Error::WrongRecipient));
+    }
+
+    #[test]
+    fn altered_wrapped_key_does_not_open() {
+        let (secret, public) = generate_keypair(&mut OsRng);
+        let key = generate_content_key(&mut OsRng);
+        let wrapped = wrap(&key, ARBITER.into(), &public, &mut OsRng);
+
+        let mut reassigned = wrapped.clone();
+        reassigned.recipient = AUDITOR.into();
+        assert_eq!(unwrap(&reassigned, &secret), Err(Error::Corrupted));
+
+        let mut flipped = wrapped.clone();
+        flipped.sealed[0] ^= 1;
+        assert_eq!(unwrap(&flipped, &secret), Err(Error::Corrupted));
+
+        let mut low_order = wrapped;
+        low_order.ephemeral_key = [0; 32];
+        assert_eq!(unwrap(&low_order, &secret), Err(Error::Corrupted));
+    }
+
+    #[test]
+    fn commitment_is_the_blake2b_256_hash() {
+        let expected = "17cdc7bca3f2a0bda60c6de5b96f82a36239b44bde397a3862d529ba8b3d7c62";
+        let hex: String = commitment(&[7; 32])
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        assert_eq!(hex, expected);
+    }
+}
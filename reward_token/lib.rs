@@ -2,9 +2,12 @@
 
 #[ink::contract]
 mod rewardtoken {
-    use ink::prelude::string::String;
+    use ink::prelude::string::{String, ToString};
     use ink::storage::Mapping;
     use scale::{Decode, Encode};
+    use shared::cid::{Cid, CidError};
+    use shared::findings::{FindingsSummary, SeverityCounts};
+    use shared::roles::{Role, RoleError, Roles};
 
     #[derive(scale::Decode, scale::Encode)]
     #[cfg_attr(
@@ -22,7 +25,8 @@ mod rewardtoken {
         pub extensions: u8,
         /// Final value amount
         pub amount: Balance,
-        ///  submitted audit report ipfs_hash
+        ///  submitted audit report ipfs_hash, the text form of the report's CID. show_report_cid
+        ///  returns the CID itself.
         pub ipfs_hash: String,
     }
 
@@ -40,21 +44,81 @@ mod rewardtoken {
     pub struct TokenMinted {
         token_id: u32,
         reward_info: Option<RewardInfo>,
+        report: Cid,
+        report_version: u32,
+        findings: SeverityCounts,
         is_positive: bool,
     }
 
+    /// emitted when the admin grants a role to an account.
+    #[ink(event)]
+    pub struct RoleGranted {
+        role: Role,
+        account: AccountId,
+        sender: AccountId,
+    }
+
+    /// emitted when a role is revoked by the admin or renounced by its holder.
+    #[ink(event)]
+    pub struct RoleRevoked {
+        role: Role,
+        account: AccountId,
+        sender: AccountId,
+    }
+
+    /// emitted when the admin proposes a successor, who still has to accept.
+    #[ink(event)]
+    pub struct AdminTransferProposed {
+        admin: AccountId,
+        proposed: AccountId,
+    }
+
+    /// emitted when the proposed admin accepts and takes over.
+    #[ink(event)]
+    pub struct AdminTransferred {
+        previous: AccountId,
+        admin: AccountId,
+    }
+
     #[ink(storage)]
     pub struct Rewardtoken {
         pub current_id: u32,
         pub balances: Mapping<AccountId, Stats>,
-        pub owner: AccountId,
+        roles: Roles,
         pub rewarded_tokens: Mapping<u32, RewardInfo>,
+        /// the version of the audit's report on escrow each token was minted for, kept apart from
+        /// RewardInfo so that tokens minted before versions existed keep decoding.
+        report_versions: Mapping<u32, u32>,
+        /// the report each token was minted for, in binary form.
+        report_cids: Mapping<u32, Cid>,
+        /// the findings of the report each token was minted for.
+        findings: Mapping<u32, FindingsSummary>,
+        /// per auditor, the findings of all their successful audits added up.
+        auditors_findings: Mapping<AccountId, SeverityCounts>,
     }
 
     #[derive(Debug, PartialEq, Eq, Encode, Decode, Clone, Copy)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
         UnAuthorisedCall,
+        AdminIsTransferOnly,
+        InvalidCid(CidError),
+        InvalidFindings,
+    }
+
+    impl From<CidError> for Error {
+        fn from(error: CidError) -> Self {
+            Error::InvalidCid(error)
+        }
+    }
+
+    impl From<RoleError> for Error {
+        fn from(error: RoleError) -> Self {
+            match error {
+                RoleError::AdminIsTransferOnly => Error::AdminIsTransferOnly,
+                RoleError::MissingRole | RoleError::NotPendingAdmin => Error::UnAuthorisedCall,
+            }
+        }
     }
 
     pub type Result<T> = core::result::Result<T, Error>;
@@ -63,22 +127,116 @@ mod rewardtoken {
         #[ink(constructor)]
         pub fn new(_owner: AccountId) -> Self {
             let current_id = u32::default();
-            let owner = _owner;
+            // the owner becomes the admin, and starts out as the only minter.
+            let mut roles = Roles::new(_owner);
+            let _ = roles.grant(_owner, Role::Minter, _owner);
             let balances = Mapping::default();
             let rewarded_tokens = Mapping::default();
             Self {
                 current_id,
-                owner,
+                roles,
                 balances,
                 rewarded_tokens,
+                report_versions: Mapping::default(),
+                report_cids: Mapping::default(),
+                findings: Mapping::default(),
+                auditors_findings: Mapping::default(),
             }
         }
 
-        /// mint function first checks that only the owner can call the contract,
+        /// has_role tells whether the account holds the role, the admin role is held by the admin only.
+        #[ink(message)]
+        pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.roles.has_role(role, account)
+        }
+
+        #[ink(message)]
+        pub fn get_pending_admin(&self) -> Option<AccountId> {
+            self.roles.pending_admin()
+        }
+
+        /// grant_role can only be called by the admin, and gives the role to the account.
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: Role, account: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            if self.roles.grant(caller, role, account)? {
+                self.env().emit_event(RoleGranted {
+                    role,
+                    account,
+                    sender: caller,
+                });
+            }
+            Ok(())
+        }
+
+        /// revoke_role can only be called by the admin, and takes the role away from the account.
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: Role, account: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            if self.roles.revoke(caller, role, account)? {
+                self.env().emit_event(RoleRevoked {
+                    role,
+                    account,
+                    sender: caller,
+                });
+            }
+            Ok(())
+        }
+
+        /// renounce_role lets the caller give up a role it holds.
+        #[ink(message)]
+        pub fn renounce_role(&mut self, role: Role) -> Result<()> {
+            let caller = self.env().caller();
+            if self.roles.renounce(caller, role)? {
+                self.env().emit_event(RoleRevoked {
+                    role,
+                    account: caller,
+                    sender: caller,
+                });
+            }
+            Ok(())
+        }
+
+        /// propose_admin is the first step of handing over the admin role, the proposed account has to
+        /// call accept_admin before anything changes, and a new proposal replaces the previous one.
+        #[ink(message)]
+        pub fn propose_admin(&mut self, new_admin: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            self.roles.propose_admin(caller, new_admin)?;
+            self.env().emit_event(AdminTransferProposed {
+                admin: caller,
+                proposed: new_admin,
+            });
+            Ok(())
+        }
+
+        /// accept_admin is called by the proposed admin to take over from the current one.
+        #[ink(message)]
+        pub fn accept_admin(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let previous = self.roles.accept_admin(caller)?;
+            self.env().emit_event(AdminTransferred {
+                previous,
+                admin: caller,
+            });
+            Ok(())
+        }
+
+        /// know_your_admin returns the account managing the roles of this contract.
+        #[ink(message)]
+        pub fn know_your_admin(&self) -> AccountId {
+            self.roles.admin()
+        }
+
+        /// mint function first checks that only a minter can call the contract,
         /// then it modifies the state of both the auditors_record(if it is a successful audit or unsuccessful one)
         /// and mints the token with auditor as the recipient and all other details like audit_id, completion_time, if it was
-        /// completed with extensions, or in what percent time, the amount, and the ipfs_hash corresponding that audit.
+        /// completed with extensions, or in what percent time, the amount, and the CID and version of the report
+        /// escrow recorded as accepted for that audit, along with the summary of that report's findings, which a successful
+        /// audit adds to the auditor's record. a hash that isn't a valid CID, or a summary escrow wouldn't have taken, is
+        /// rejected before anything changes.
         #[ink(message)]
+        #[allow(clippy::too_many_arguments)]
         pub fn mint(
             &mut self,
             _recipient: AccountId,
@@ -87,17 +245,23 @@ mod rewardtoken {
             _extensions: u8,
             _amount: Balance,
             _ipfs_hash: String,
+            _report_version: u32,
+            _findings: FindingsSummary,
             positive_or_not: bool,
         ) -> Result<()> {
-            let caller = self.env().caller();
-            if self.owner != caller {
-                return Err(Error::UnAuthorisedCall);
+            self.roles.ensure_role(Role::Minter, self.env().caller())?;
+            let report = Cid::parse(&_ipfs_hash)?;
+            if !_findings.is_valid() {
+                return Err(Error::InvalidFindings);
             }
             if positive_or_not {
                 let mut _stat = self.balances.get(&_recipient).unwrap_or_default();
 
                 _stat.successful_audits = _stat.successful_audits + 1;
                 self.balances.insert(&_recipient, &_stat);
+                let record = self.show_auditors_findings(_recipient);
+                self.auditors_findings
+                    .insert(_recipient, &record.saturating_add(_findings.counts));
             } else {
                 let mut _stat = self.balances.get(_recipient).unwrap_or_default();
                 _stat.unsuccessful_audits = _stat.unsuccessful_audits + 1;
@@ -109,12 +273,18 @@ mod rewardtoken {
                 completion_time: _completion_time,
                 extensions: _extensions,
                 amount: _amount,
-                ipfs_hash: _ipfs_hash,
+                ipfs_hash: report.to_string(),
             };
             self.rewarded_tokens.insert(&self.current_id, &_reward_info);
+            self.report_versions.insert(self.current_id, &_report_version);
+            self.report_cids.insert(self.current_id, &report);
+            self.findings.insert(self.current_id, &_findings);
             self.env().emit_event(TokenMinted{
                 token_id: self.current_id,
                 reward_info: Some(_reward_info),
+                report,
+                report_version: _report_version,
+                findings: _findings.counts,
                 is_positive: positive_or_not, 
             });
             self.current_id = self.current_id + 1;
@@ -128,12 +298,44 @@ mod rewardtoken {
             self.balances.get(&auditor)
         }
 
+        /// show_auditors_findings returns the findings of all the auditor's successful audits, counted per severity.
+        #[ink(message)]
+        pub fn show_auditors_findings(&self, auditor: AccountId) -> SeverityCounts {
+            self.auditors_findings.get(auditor).unwrap_or_default()
+        }
+
+        /// show_findings returns the summary of the findings of the report the reward token was minted for. tokens
+        /// minted before findings were summarised have none.
+        #[ink(message)]
+        pub fn show_findings(&self, reward_id: u32) -> Option<FindingsSummary> {
+            self.findings.get(reward_id)
+        }
+
         /// show_reward_details returns the RewardInfo/the metadata corresponding to the
         /// reward token entered.
         #[ink(message)]
         pub fn show_reward_details(&self, reward_id: u32) -> Option<RewardInfo> {
             self.rewarded_tokens.get(&reward_id)
         }
+
+        /// show_report_version returns the version of the audit's report the reward token was minted for,
+        /// which is 0 for the tokens minted before reports had versions.
+        #[ink(message)]
+        pub fn show_report_version(&self, reward_id: u32) -> Option<u32> {
+            self.rewarded_tokens
+                .contains(reward_id)
+                .then(|| self.report_versions.get(reward_id).unwrap_or_default())
+        }
+
+        /// show_report_cid returns the CID of the report the reward token was minted for. for tokens
+        /// minted before reports were stored as CIDs it is read from their ipfs_hash, if that is one.
+        #[ink(message)]
+        pub fn show_report_cid(&self, reward_id: u32) -> Option<Cid> {
+            self.report_cids.get(reward_id).or_else(|| {
+                let reward_info = self.rewarded_tokens.get(reward_id)?;
+                Cid::parse(&reward_info.ipfs_hash).ok()
+            })
+        }
     }
 }
 
@@ -142,6 +344,9 @@ mod test_cases {
     use super::*;
     #[cfg(feature = "ink-experimental-engine")]
     use crate::digital_certificate::digital_certificate;
+    use shared::findings::{FindingsSummary, SeverityCounts};
+
+    const REPORT: &str = "bafybeierhgbz4zp2x2u67urqrgfnrnlukciupzenpqpipiz5nwtq7uxpx4";
 
     #[test]
     fn test_assert_owner() {
@@ -150,8 +355,9 @@ mod test_cases {
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
         ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
         let contract = rewardtoken::Rewardtoken::new(accounts.alice);
-        let contract_owner = contract.owner;
+        let contract_owner = contract.know_your_admin();
         assert_eq!(contract_owner, accounts.alice);
+        assert!(contract.has_role(shared::roles::Role::Minter, accounts.alice));
     }
 
     #[test]
@@ -162,11 +368,80 @@ mod test_cases {
         ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
         let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-        let hash = "asdf";
-        let _res = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), false);
+        let hash = REPORT;
+        let _res = contract.mint(
+            accounts.bob,
+            1,
+            100,
+            0,
+            100,
+            hash.to_string(),
+            0,
+            FindingsSummary::default(),
+            false,
+        );
         assert!(_res.is_err());
     }
 
+    #[test]
+    fn test_granted_minter_can_mint() {
+        //testcase to validate that a minter granted by the admin can mint, and loses the right once revoked
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let _x = contract.grant_role(shared::roles::Role::Minter, accounts.charlie);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        let hash = REPORT;
+        assert!(contract
+            .mint(
+                accounts.bob,
+                1,
+                100,
+                0,
+                100,
+                hash.to_string(),
+                0,
+                FindingsSummary::default(),
+                true
+            )
+            .is_ok());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let _y = contract.revoke_role(shared::roles::Role::Minter, accounts.charlie);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        let z = contract.mint(
+            accounts.bob,
+            2,
+            100,
+            0,
+            100,
+            hash.to_string(),
+            0,
+            FindingsSummary::default(),
+            true,
+        );
+        assert_eq!(z, Err(rewardtoken::Error::UnAuthorisedCall));
+    }
+
+    #[test]
+    fn test_admin_transfer_needs_acceptance() {
+        //testcase to validate that the admin only changes once the proposed account accepts
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let _x = contract.propose_admin(accounts.charlie);
+        assert_eq!(contract.know_your_admin(), accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(
+            contract.accept_admin(),
+            Err(rewardtoken::Error::UnAuthorisedCall)
+        );
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        let _y = contract.accept_admin();
+        assert_eq!(contract.know_your_admin(), accounts.charlie);
+    }
+
     #[test]
     fn test_successful_audits_increment() {
         //testcase to validate the successful increment in successful audits variable
@@ -174,8 +449,18 @@ mod test_cases {
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
         ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
         let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
-        let hash = "asdf";
-        let _x = contract.mint(accounts.bob, 1, 100, 5, 100, hash.to_string(), true);
+        let hash = REPORT;
+        let _x = contract.mint(
+            accounts.bob,
+            1,
+            100,
+            5,
+            100,
+            hash.to_string(),
+            0,
+            FindingsSummary::default(),
+            true,
+        );
         assert_eq!(
             contract
                 .show_auditors_record(accounts.bob)
@@ -192,8 +477,18 @@ mod test_cases {
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
         ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
         let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
-        let hash = "asdf";
-        let _x = contract.mint(accounts.bob, 1, 100, 5, 100, hash.to_string(), false);
+        let hash = REPORT;
+        let _x = contract.mint(
+            accounts.bob,
+            1,
+            100,
+            5,
+            100,
+            hash.to_string(),
+            0,
+            FindingsSummary::default(),
+            false,
+        );
         assert_eq!(
             contract
                 .show_auditors_record(accounts.bob)
@@ -210,9 +505,121 @@ mod test_cases {
         ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
         ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
         let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
-        let hash = "asdf";
-        let _x = contract.mint(accounts.bob, 1, 100, 0, 100, hash.to_string(), true);
+        let hash = REPORT;
+        let _x = contract.mint(
+            accounts.bob,
+            1,
+            100,
+            0,
+            100,
+            hash.to_string(),
+            2,
+            FindingsSummary::default(),
+            true,
+        );
 
         assert_eq!(contract.show_reward_details(0).unwrap().amount, 100);
+        assert_eq!(contract.show_reward_details(0).unwrap().ipfs_hash, REPORT);
+        assert_eq!(contract.show_report_version(0), Some(2));
+        assert_eq!(contract.show_report_version(1), None);
+        assert_eq!(contract.show_report_cid(0).unwrap().to_string(), REPORT);
+        assert_eq!(contract.show_report_cid(1), None);
+    }
+
+    #[test]
+    fn test_malformed_report_hash_is_rejected() {
+        //testcase to validate that a report hash which isn't a CID mints nothing
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let x = contract.mint(
+            accounts.bob,
+            1,
+            100,
+            0,
+            100,
+            "asdf".to_string(),
+            0,
+            FindingsSummary::default(),
+            true,
+        );
+        assert_eq!(
+            x,
+            Err(rewardtoken::Error::InvalidCid(
+                shared::cid::CidError::UnsupportedMultibase
+            ))
+        );
+        let y = contract.mint(
+            accounts.bob,
+            1,
+            100,
+            0,
+            100,
+            "QmTooShort".to_string(),
+            0,
+            FindingsSummary::default(),
+            true,
+        );
+        assert!(matches!(y, Err(rewardtoken::Error::InvalidCid(_))));
+        assert!(contract.show_auditors_record(accounts.bob).is_none());
+        assert!(contract.show_reward_details(0).is_none());
+    }
+
+    #[test]
+    fn test_findings_add_up_in_auditors_record() {
+        //testcase to validate that the findings of successful audits are added to the auditor's record
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        let mut contract = rewardtoken::Rewardtoken::new(accounts.alice);
+        let findings = FindingsSummary {
+            counts: SeverityCounts {
+                critical: 1,
+                medium: 2,
+                ..Default::default()
+            },
+            findings: Vec::new(),
+        };
+        let mut incomplete = findings.clone();
+        incomplete
+            .findings
+            .push(shared::cid::Cid::parse(REPORT).unwrap());
+        let x = contract.mint(
+            accounts.bob,
+            1,
+            100,
+            0,
+            100,
+            REPORT.to_string(),
+            0,
+            incomplete,
+            true,
+        );
+        assert_eq!(x, Err(rewardtoken::Error::InvalidFindings));
+        for (audit_id, positive) in [(1, true), (2, true), (3, false)] {
+            let y = contract.mint(
+                accounts.bob,
+                audit_id,
+                100,
+                0,
+                100,
+                REPORT.to_string(),
+                0,
+                findings.clone(),
+                positive,
+            );
+            assert!(y.is_ok());
+        }
+        assert_eq!(contract.show_findings(2), Some(findings));
+        assert_eq!(contract.show_findings(3), None);
+        let record = contract.show_auditors_findings(accounts.bob);
+        assert_eq!(record.critical, 2);
+        assert_eq!(record.medium, 4);
+        assert_eq!(record.total(), 6);
+        assert_eq!(
+            contract.show_auditors_findings(accounts.charlie),
+            SeverityCounts::default()
+        );
     }
 }
@@ -0,0 +1,345 @@
+//! Content identifiers for reports and evidence.
+//!
+//! Reports and evidence live on IPFS, and the contracts only keep their [`Cid`]. A CID is
+//! parsed from its text form once, when it is handed to a contract, and kept in its binary
+//! form, which is about half the size and can't hold anything that isn't a CID. Both
+//! CIDv0 (`Qm...`, base58btc) and CIDv1 in base32, base58btc or base16 are accepted, as
+//! long as the content type and the hash function are ones IPFS uses for files.
+
+use core::fmt;
+use ink::prelude::{string::String, vec::Vec};
+
+/// `dag-pb`, the content type of every CIDv0.
+pub const DAG_PB: u64 = 0x70;
+
+/// the content types a report or evidence can have: raw, dag-pb, dag-cbor, dag-json and json.
+const CODECS: [u64; 5] = [0x55, DAG_PB, 0x71, 0x0129, 0x0200];
+
+/// the hash functions accepted, with the digest length each of them produces: sha2-256,
+/// sha2-512, sha3-512, sha3-256, keccak-256, blake3 and blake2b-256.
+const HASHES: [(u64, usize); 7] = [
+    (0x12, 32),
+    (0x13, 64),
+    (0x14, 64),
+    (0x16, 32),
+    (0x1b, 32),
+    (0x1e, 32),
+    (0xb220, 32),
+];
+
+const SHA2_256: u8 = 0x12;
+const BASE58: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BASE32: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum CidError {
+    Empty,
+    /// the text starts with a multibase prefix other than `b`, `B`, `z`, `f` or `F`.
+    UnsupportedMultibase,
+    /// the text has characters that don't belong to its base.
+    InvalidEncoding,
+    /// a varint runs past the end of the CID or over 9 bytes.
+    InvalidVarint,
+    UnsupportedVersion,
+    UnsupportedCodec,
+    UnsupportedHash,
+    /// the digest isn't as long as the multihash says, or as the hash function makes it.
+    InvalidDigestLength,
+}
+
+/// a validated CID, in binary form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Cid(Vec<u8>);
+
+impl Cid {
+    /// parses a CID from its text form.
+    pub fn parse(text: &str) -> Result<Cid, CidError> {
+        let bytes = text.as_bytes();
+        let (prefix, rest) = match bytes.split_first() {
+            Some(split) => split,
+            None => return Err(CidError::Empty),
+        };
+        // a CIDv0 is a bare base58btc multihash, always 46 characters starting with "Qm".
+        if bytes.len() == 46 && bytes.starts_with(b"Qm") {
+            return Cid::from_bytes(&base58_decode(bytes)?);
+        }
+        let decoded = match prefix {
+            b'b' => base32_decode(rest, false)?,
+            b'B' => base32_decode(rest, true)?,
+            b'z' => base58_decode(rest)?,
+            b'f' | b'F' => base16_decode(rest)?,
+            _ => return Err(CidError::UnsupportedMultibase),
+        };
+        Cid::from_bytes(&decoded)
+    }
+
+    /// checks a CID in binary form.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Cid, CidError> {
+        if bytes.len() == 34 && bytes[0] == SHA2_256 && bytes[1] == 32 {
+            return Ok(Cid(bytes.to_vec()));
+        }
+        let (version, rest) = read_varint(bytes)?;
+        if version != 1 {
+            return Err(CidError::UnsupportedVersion);
+        }
+        let (codec, rest) = read_varint(rest)?;
+        if !CODECS.contains(&codec) {
+            return Err(CidError::UnsupportedCodec);
+        }
+        let (hash, rest) = read_varint(rest)?;
+        let (length, digest) = read_varint(rest)?;
+        let expected = HASHES
+            .iter()
+            .find(|(code, _)| *code == hash)
+            .map(|(_, length)| *length)
+            .ok_or(CidError::UnsupportedHash)?;
+        if length != expected as u64 || digest.len() != expected {
+            return Err(CidError::InvalidDigestLength);
+        }
+        Ok(Cid(bytes.to_vec()))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn version(&self) -> u64 {
+        if self.0[0] == SHA2_256 {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// the content type, dag-pb for a CIDv0.
+    pub fn codec(&self) -> u64 {
+        match self.version() {
+            0 => DAG_PB,
+            _ => read_varint(&self.0[1..]).map_or(0, |(codec, _)| codec),
+        }
+    }
+}
+
+impl fmt::Display for Cid {
+    /// a CIDv0 in base58btc, a CIDv1 in lowercase base32, the way IPFS prints them.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.version() {
+            0 => f.write_str(&base58_encode(&self.0)),
+            _ => {
+                f.write_str("b")?;
+                f.write_str(&base32_encode(&self.0))
+            }
+        }
+    }
+}
+
+impl scale::Encode for Cid {
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        self.0.encode_to(dest)
+    }
+}
+
+impl scale::EncodeLike for Cid {}
+
+/// decoding checks the CID again, so that neither storage nor a call can hold an invalid one.
+impl scale::Decode for Cid {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let bytes = Vec::<u8>::decode(input)?;
+        Cid::from_bytes(&bytes).map_err(|_| "invalid CID".into())
+    }
+}
+
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]), CidError> {
+    let mut value = 0u64;
+    for (i, byte) in bytes.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[i + 1..]));
+        }
+    }
+    Err(CidError::InvalidVarint)
+}
+
+fn base58_decode(text: &[u8]) -> Result<Vec<u8>, CidError> {
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in text {
+        let mut carry = BASE58
+            .iter()
+            .position(|a| a == c)
+            .ok_or(CidError::InvalidEncoding)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = text.iter().take_while(|c| **c == b'1').count();
+    let mut decoded = [0u8].repeat(zeros);
+    decoded.extend(bytes);
+    Ok(decoded)
+}
+
+fn base58_encode(bytes: &[u8]) -> String {
+    let mut digits: Vec<u8> = Vec::new();
+    for byte in bytes {
+        let mut carry = u32::from(*byte);
+        for digit in digits.iter_mut().rev() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.insert(0, (carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    // every leading zero byte is written as a "1".
+    bytes
+        .iter()
+        .take_while(|b| **b == 0)
+        .map(|_| b'1')
+        .chain(digits.iter().map(|d| BASE58[usize::from(*d)]))
+        .map(char::from)
+        .collect()
+}
+
+fn base32_decode(text: &[u8], upper: bool) -> Result<Vec<u8>, CidError> {
+    let mut decoded = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in text {
+        let c = if upper { c.to_ascii_lowercase() } else { *c };
+        let value = BASE32
+            .iter()
+            .position(|a| *a == c)
+            .ok_or(CidError::InvalidEncoding)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(decoded)
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len() * 8 / 5 + 1);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for byte in bytes {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            text.push(char::from(BASE32[((buffer >> bits) & 31) as usize]));
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        text.push(char::from(BASE32[((buffer << (5 - bits)) & 31) as usize]));
+    }
+    text
+}
+
+fn base16_decode(text: &[u8]) -> Result<Vec<u8>, CidError> {
+    if text.len() % 2 == 1 {
+        return Err(CidError::InvalidEncoding);
+    }
+    text.chunks(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16);
+            let low = (pair[1] as char).to_digit(16);
+            match (high, low) {
+                (Some(high), Some(low)) => Ok((high * 16 + low) as u8),
+                _ => Err(CidError::InvalidEncoding),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink::prelude::string::ToString;
+
+    const V0: &str = "QmY7Yh4UquoXHLPFo2XbhXkhBvFoPwmQUSa92pxnxjQuPU";
+    const V1: &str = "bafybeierhgbz4zp2x2u67urqrgfnrnlukciupzenpqpipiz5nwtq7uxpx4";
+    const RAW: &str = "bafkreibm6jg3ux5qumhcn2b3flc3tyu6dmlb4xa7u5bf44yegnrjhc4yeq";
+
+    #[test]
+    fn parses_both_versions_to_compact_bytes() {
+        let v0 = Cid::parse(V0).unwrap();
+        assert_eq!(v0.version(), 0);
+        assert_eq!(v0.codec(), DAG_PB);
+        assert_eq!(v0.as_bytes().len(), 34);
+        assert_eq!(v0.to_string(), V0);
+
+        let v1 = Cid::parse(V1).unwrap();
+        assert_eq!(v1.version(), 1);
+        assert_eq!(v1.codec(), DAG_PB);
+        // the same content, the CIDv1 only adds the version and the codec.
+        assert_eq!(&v1.as_bytes()[2..], v0.as_bytes());
+        assert_eq!(v1.to_string(), V1);
+    }
+
+    #[test]
+    fn every_multibase_gives_the_same_cid() {
+        let raw = Cid::parse(RAW).unwrap();
+        assert_eq!(raw.codec(), 0x55);
+        assert_eq!(Cid::parse(&RAW.to_uppercase()).unwrap(), raw);
+        assert_eq!(
+            Cid::parse("zb2rhZfjRh2FHHB2RkHVEvL2vJnCTcu7kwRqgVsf9gpkLgteo").unwrap(),
+            raw
+        );
+        assert_eq!(
+            Cid::parse("f015512202cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+                .unwrap(),
+            raw
+        );
+    }
+
+    #[test]
+    fn malformed_cids_are_rejected() {
+        assert_eq!(Cid::parse(""), Err(CidError::Empty));
+        assert_eq!(
+            Cid::parse("good work there"),
+            Err(CidError::UnsupportedMultibase)
+        );
+        assert_eq!(
+            Cid::parse("QmY7Yh4UquoXHLPFo2XbhXkhBvFoPwmQUSa92pxnxjQu0U"),
+            Err(CidError::InvalidEncoding)
+        );
+        // cut short, and with a made up codec.
+        assert_eq!(
+            Cid::parse(&V1[..V1.len() - 4]),
+            Err(CidError::InvalidDigestLength)
+        );
+        assert_eq!(Cid::parse("f01991220"), Err(CidError::UnsupportedCodec));
+        assert_eq!(Cid::parse("f0255"), Err(CidError::UnsupportedVersion));
+        assert_eq!(Cid::parse("f01ff"), Err(CidError::InvalidVarint));
+    }
+
+    #[test]
+    fn decoding_checks_the_bytes() {
+        use scale::{Decode, Encode};
+        let cid = Cid::parse(V1).unwrap();
+        assert_eq!(Cid::decode(&mut &cid.encode()[..]).unwrap(), cid);
+        assert!(Cid::decode(&mut &b"good work there".to_vec().encode()[..]).is_err());
+    }
+}
@@ -0,0 +1,31 @@
+//! Evidence attached to a disputed audit.
+//!
+//! Escrow keeps the evidence the patron and the auditor submit while their audit waits
+//! for the arbiters, and voting reads it back for the poll deciding on the audit, so both
+//! contracts need the same encoding of an entry.
+
+use crate::cid::Cid;
+use crate::time::Timestamp;
+use crate::AccountId;
+use ink::prelude::string::String;
+
+/// the longest label an entry can have, in bytes.
+pub const MAX_EVIDENCE_LABEL_LEN: usize = 64;
+
+/// the most entries each party can attach to one audit.
+pub const MAX_EVIDENCE_PER_PARTY: u32 = 16;
+
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Evidence {
+    /// the position of the entry among the audit's evidence.
+    pub id: u32,
+    pub submitter: AccountId,
+    pub cid: Cid,
+    /// a short description of what the entry shows, like "reproduction of H-1".
+    pub label: String,
+    pub submitted_at: Timestamp,
+}
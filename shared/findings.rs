@@ -0,0 +1,115 @@
+//! What an audit found.
+//!
+//! The auditor submits a summary of the findings with every version of the report, and escrow
+//! keeps it with that version. reward_token is given the summary of the accepted version when
+//! the auditor's token is minted, so that an auditor's record shows what their audits found and
+//! not only how many were accepted.
+
+use crate::cid::Cid;
+use ink::prelude::vec::Vec;
+
+/// the most findings a summary can list the hashes of.
+pub const MAX_FINDINGS: usize = 64;
+
+/// how many findings there are of each severity.
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct SeverityCounts {
+    pub critical: u32,
+    pub high: u32,
+    pub medium: u32,
+    pub low: u32,
+    pub informational: u32,
+}
+
+impl SeverityCounts {
+    pub fn total(&self) -> u32 {
+        self.critical
+            .saturating_add(self.high)
+            .saturating_add(self.medium)
+            .saturating_add(self.low)
+            .saturating_add(self.informational)
+    }
+
+    /// adds the counts of another summary, e.g. to an auditor's record.
+    pub fn saturating_add(self, other: SeverityCounts) -> SeverityCounts {
+        SeverityCounts {
+            critical: self.critical.saturating_add(other.critical),
+            high: self.high.saturating_add(other.high),
+            medium: self.medium.saturating_add(other.medium),
+            low: self.low.saturating_add(other.low),
+            informational: self.informational.saturating_add(other.informational),
+        }
+    }
+}
+
+#[derive(scale::Decode, scale::Encode, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct FindingsSummary {
+    pub counts: SeverityCounts,
+    /// the CID of each finding's write-up, from the most severe down. either empty or one for
+    /// every finding counted.
+    pub findings: Vec<Cid>,
+}
+
+impl FindingsSummary {
+    /// whether the summary lists either no hashes, or one for every finding and at most
+    /// MAX_FINDINGS of them.
+    pub fn is_valid(&self) -> bool {
+        self.findings.is_empty()
+            || (self.findings.len() <= MAX_FINDINGS
+                && self.findings.len() == self.counts.total() as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V0: &str = "QmY7Yh4UquoXHLPFo2XbhXkhBvFoPwmQUSa92pxnxjQuPU";
+
+    #[test]
+    fn hashes_are_optional_but_complete() {
+        let counts = SeverityCounts {
+            critical: 1,
+            low: 1,
+            ..Default::default()
+        };
+        let finding = Cid::parse(V0).unwrap();
+        let mut summary = FindingsSummary {
+            counts,
+            findings: Vec::new(),
+        };
+        assert!(summary.is_valid());
+        summary.findings.push(finding.clone());
+        assert!(!summary.is_valid());
+        summary.findings.push(finding);
+        assert!(summary.is_valid());
+        assert!(FindingsSummary::default().is_valid());
+    }
+
+    #[test]
+    fn counts_add_up() {
+        let one_each = SeverityCounts {
+            critical: 1,
+            high: 1,
+            medium: 1,
+            low: 1,
+            informational: 1,
+        };
+        assert_eq!(one_each.total(), 5);
+        let record = one_each.saturating_add(SeverityCounts {
+            critical: u32::MAX,
+            ..Default::default()
+        });
+        assert_eq!(record.critical, u32::MAX);
+        assert_eq!(record.high, 1);
+        assert_eq!(record.total(), u32::MAX);
+    }
+}
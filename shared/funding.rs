@@ -0,0 +1,115 @@
+//! Audits funded by many sponsors.
+//!
+//! A funding round collects contributions from any account towards an audit, and escrow keeps
+//! what each sponsor put in. Whatever the audit would hand back to its patron, because it
+//! expired, was cut short or came in cheaper, goes back to the sponsors instead, split in
+//! proportion to their contributions with [`split`].
+
+use crate::AccountId;
+use ink::prelude::vec::Vec;
+
+pub type Balance = <ink::env::DefaultEnvironment as ink::env::Environment>::Balance;
+
+/// the most sponsors a round can have, as every refund pays each of them.
+pub const MAX_SPONSORS: u32 = 32;
+
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Contribution {
+    pub sponsor: AccountId,
+    /// everything the sponsor contributed to the round.
+    pub amount: Balance,
+    /// everything the sponsor was refunded since.
+    pub refunded: Balance,
+}
+
+/// splits amount in proportion to the contributions, each share rounded down and the last
+/// contribution getting what is left over, so that the shares add up to amount exactly.
+pub fn split(amount: Balance, contributions: &[Balance]) -> Vec<Balance> {
+    let raised = contributions.iter().sum::<Balance>();
+    let mut shares: Vec<Balance> = contributions
+        .iter()
+        .map(|contribution| pro_rata(amount, *contribution, raised))
+        .collect();
+    let handed_out = shares.iter().sum::<Balance>();
+    if let Some(last) = shares.last_mut() {
+        *last += amount - handed_out;
+    }
+    shares
+}
+
+// amount * part / whole rounded down, for part at most whole. the product is worked out in 256
+// bits, as it overflows a Balance for large enough values.
+fn pro_rata(amount: Balance, part: Balance, whole: Balance) -> Balance {
+    if whole == 0 {
+        return 0;
+    }
+    let (high, low) = widening_mul(amount, part);
+    let mut remainder: Balance = 0;
+    let mut quotient: Balance = 0;
+    for bit in (0..256).rev() {
+        let next = if bit >= 128 {
+            (high >> (bit - 128)) & 1
+        } else {
+            (low >> bit) & 1
+        };
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | next;
+        if carry == 1 || remainder >= whole {
+            remainder = remainder.wrapping_sub(whole);
+            // the quotient is at most amount, so it has no bits above the low half.
+            if bit < 128 {
+                quotient |= 1 << bit;
+            }
+        }
+    }
+    quotient
+}
+
+fn widening_mul(a: Balance, b: Balance) -> (Balance, Balance) {
+    const HALF: u32 = 64;
+    const MASK: Balance = u64::MAX as Balance;
+    let (a_high, a_low) = (a >> HALF, a & MASK);
+    let (b_high, b_low) = (b >> HALF, b & MASK);
+    let low = a_low * b_low;
+    let cross = a_low * b_high;
+    let other_cross = a_high * b_low;
+    let middle = (low >> HALF) + (cross & MASK) + (other_cross & MASK);
+    let high = a_high * b_high + (cross >> HALF) + (other_cross >> HALF) + (middle >> HALF);
+    (high, (middle << HALF) | (low & MASK))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_follow_the_contributions() {
+        assert_eq!(split(100, &[50, 30, 20]), [50, 30, 20]);
+        assert_eq!(split(10, &[50, 30, 20]), [5, 3, 2]);
+        // what rounding leaves goes to the last sponsor.
+        assert_eq!(split(10, &[1, 1, 1]), [3, 3, 4]);
+        assert_eq!(split(0, &[1, 2]), [0, 0]);
+        assert!(split(10, &[]).is_empty());
+    }
+
+    #[test]
+    fn large_values_do_not_overflow() {
+        let million_dollars = 1_000_000 * 10u128.pow(18);
+        let shares = split(
+            million_dollars,
+            &[million_dollars / 4, million_dollars / 4 * 3],
+        );
+        assert_eq!(shares, [million_dollars / 4, million_dollars / 4 * 3]);
+        let half = Balance::MAX / 2;
+        assert_eq!(split(Balance::MAX, &[half, half + 1]), [half, half + 1]);
+        assert_eq!(split(half, &[half, half + 1]), [half / 2, half - half / 2]);
+        assert_eq!(
+            pro_rata(Balance::MAX, Balance::MAX - 1, Balance::MAX),
+            Balance::MAX - 1
+        );
+    }
+}
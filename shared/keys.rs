@@ -0,0 +1,35 @@
+//! Keys to reports on private codebases.
+//!
+//! A report that shouldn't be world-readable is encrypted with a content key before it goes
+//! to IPFS. Everyone who may read it registers an x25519 public key on escrow, and is given
+//! the content key wrapped to that public key: sealed under a key agreed between it and a
+//! one-off ephemeral key, so that only the holder of the matching secret key can unwrap it.
+//! Wrapping and unwrapping happen off-chain, with the report_keys crate, and the contracts
+//! only store what comes out of it.
+
+use crate::AccountId;
+use ink::prelude::vec::Vec;
+
+/// an x25519 public key.
+pub type PublicKey = [u8; 32];
+
+/// the length of a wrapped content key, the 32 byte key and the 16 byte tag sealing it.
+pub const WRAPPED_KEY_LEN: usize = 48;
+
+/// the most recipients a content key can be wrapped for in one call.
+pub const MAX_RECIPIENTS_PER_CALL: u32 = 16;
+
+#[derive(scale::Decode, scale::Encode, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct WrappedKey {
+    pub recipient: AccountId,
+    /// the public key the content key was wrapped to, the one the recipient registered.
+    pub recipient_key: PublicKey,
+    /// the public half of the one-off key the content key was wrapped with.
+    pub ephemeral_key: PublicKey,
+    /// the content key sealed, WRAPPED_KEY_LEN bytes long.
+    pub sealed: Vec<u8>,
+}
@@ -0,0 +1,13 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Types and storage building blocks shared by the escrow, voting and reward_token contracts.
+
+pub mod cid;
+pub mod evidence;
+pub mod findings;
+pub mod funding;
+pub mod keys;
+pub mod roles;
+pub mod time;
+
+pub type AccountId = <ink::env::DefaultEnvironment as ink::env::Environment>::AccountId;
@@ -0,0 +1,254 @@
+//! Role based access control for the contracts.
+//!
+//! Every contract keeps a [`Roles`] in its storage and checks it before doing anything
+//! privileged. The admin is a single account, it can grant and revoke all the other roles,
+//! and is handed over in two steps: the current admin proposes a successor, and the
+//! successor has to accept, so that a typo in the address can't lock the contract.
+//!
+//! The admin and the proposed admin live in their own storage cells, like the members, so
+//! that a [`Roles`] adds nothing to the packed root of the contract keeping it.
+//!
+//! ink! events can't be shared between contracts, so the contracts define the events
+//! and messages themselves and only delegate the bookkeeping to this module.
+
+use crate::AccountId;
+use ink::storage::{Lazy, Mapping};
+
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Role {
+    /// the single account that manages all the other roles.
+    Admin,
+    /// can pause and unpause a contract.
+    Pauser,
+    /// can change the platform fees.
+    FeeManager,
+    /// can open polls and pick the arbiters voting on them.
+    ArbiterManager,
+    /// can mint reward tokens.
+    Minter,
+}
+
+#[derive(scale::Decode, scale::Encode, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RoleError {
+    /// the caller doesn't hold the role needed for the call.
+    MissingRole,
+    /// the admin role can only change hands through `propose_admin`/`accept_admin`.
+    AdminIsTransferOnly,
+    /// the caller isn't the account proposed as the next admin.
+    NotPendingAdmin,
+}
+
+#[ink::storage_item]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Roles {
+    admin: Lazy<AccountId>,
+    pending_admin: Lazy<Option<AccountId>>,
+    members: Mapping<(Role, AccountId), ()>,
+}
+
+impl Roles {
+    pub fn new(admin: AccountId) -> Self {
+        let mut roles = Self {
+            admin: Lazy::default(),
+            pending_admin: Lazy::default(),
+            members: Mapping::default(),
+        };
+        roles.admin.set(&admin);
+        roles
+    }
+
+    /// the admin, or the zero account, which no one holds the key to, if none was ever set.
+    pub fn admin(&self) -> AccountId {
+        self.admin.get().unwrap_or_else(|| AccountId::from([0; 32]))
+    }
+
+    pub fn pending_admin(&self) -> Option<AccountId> {
+        self.pending_admin.get().flatten()
+    }
+
+    /// sets the admin of a contract whose storage predates roles, returning false, and
+    /// leaving it alone, if there already is one.
+    pub fn adopt_admin(&mut self, admin: AccountId) -> bool {
+        if self.admin.get().is_some() {
+            return false;
+        }
+        self.admin.set(&admin);
+        true
+    }
+
+    pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+        match role {
+            Role::Admin => self.admin.get() == Some(account),
+            _ => self.members.contains((role, account)),
+        }
+    }
+
+    pub fn ensure_role(&self, role: Role, account: AccountId) -> Result<(), RoleError> {
+        if self.has_role(role, account) {
+            Ok(())
+        } else {
+            Err(RoleError::MissingRole)
+        }
+    }
+
+    /// grants `role` to `account`, returning false if it already held it.
+    pub fn grant(
+        &mut self,
+        caller: AccountId,
+        role: Role,
+        account: AccountId,
+    ) -> Result<bool, RoleError> {
+        self.ensure_role(Role::Admin, caller)?;
+        if role == Role::Admin {
+            return Err(RoleError::AdminIsTransferOnly);
+        }
+        Ok(self.members.insert((role, account), &()).is_none())
+    }
+
+    /// revokes `role` from `account`, returning false if it didn't hold it.
+    pub fn revoke(
+        &mut self,
+        caller: AccountId,
+        role: Role,
+        account: AccountId,
+    ) -> Result<bool, RoleError> {
+        self.ensure_role(Role::Admin, caller)?;
+        if role == Role::Admin {
+            return Err(RoleError::AdminIsTransferOnly);
+        }
+        Ok(self.remove(role, account))
+    }
+
+    /// gives up a role held by the caller, returning false if it didn't hold it.
+    pub fn renounce(&mut self, caller: AccountId, role: Role) -> Result<bool, RoleError> {
+        if role == Role::Admin {
+            return Err(RoleError::AdminIsTransferOnly);
+        }
+        Ok(self.remove(role, caller))
+    }
+
+    /// first step of the admin transfer, replaces any earlier proposal.
+    pub fn propose_admin(
+        &mut self,
+        caller: AccountId,
+        new_admin: AccountId,
+    ) -> Result<(), RoleError> {
+        self.ensure_role(Role::Admin, caller)?;
+        self.pending_admin.set(&Some(new_admin));
+        Ok(())
+    }
+
+    /// second step of the admin transfer, called by the proposed admin. returns the previous admin.
+    pub fn accept_admin(&mut self, caller: AccountId) -> Result<AccountId, RoleError> {
+        if self.pending_admin() != Some(caller) {
+            return Err(RoleError::NotPendingAdmin);
+        }
+        let previous = self.admin();
+        self.admin.set(&caller);
+        self.pending_admin.set(&None);
+        Ok(previous)
+    }
+
+    fn remove(&mut self, role: Role, account: AccountId) -> bool {
+        let held = self.members.contains((role, account));
+        self.members.remove((role, account));
+        held
+    }
+}
+
+#[cfg(test)]
+mod test_cases {
+    use super::*;
+
+    fn accounts() -> ink::env::test::DefaultAccounts<ink::env::DefaultEnvironment> {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        accounts
+    }
+
+    #[test]
+    fn test_only_admin_grants_and_revokes() {
+        let accounts = accounts();
+        let mut roles = Roles::new(accounts.alice);
+        assert_eq!(
+            roles.grant(accounts.bob, Role::Pauser, accounts.bob),
+            Err(RoleError::MissingRole)
+        );
+        assert_eq!(roles.grant(accounts.alice, Role::Pauser, accounts.bob), Ok(true));
+        assert_eq!(roles.grant(accounts.alice, Role::Pauser, accounts.bob), Ok(false));
+        assert!(roles.has_role(Role::Pauser, accounts.bob));
+        assert!(!roles.has_role(Role::Minter, accounts.bob));
+        assert_eq!(
+            roles.revoke(accounts.bob, Role::Pauser, accounts.bob),
+            Err(RoleError::MissingRole)
+        );
+        assert_eq!(roles.revoke(accounts.alice, Role::Pauser, accounts.bob), Ok(true));
+        assert!(!roles.has_role(Role::Pauser, accounts.bob));
+    }
+
+    #[test]
+    fn test_renounce_own_role() {
+        let accounts = accounts();
+        let mut roles = Roles::new(accounts.alice);
+        let _x = roles.grant(accounts.alice, Role::Minter, accounts.bob);
+        assert_eq!(roles.renounce(accounts.bob, Role::Minter), Ok(true));
+        assert_eq!(roles.renounce(accounts.bob, Role::Minter), Ok(false));
+        assert!(!roles.has_role(Role::Minter, accounts.bob));
+    }
+
+    #[test]
+    fn test_admin_role_is_transfer_only() {
+        let accounts = accounts();
+        let mut roles = Roles::new(accounts.alice);
+        assert_eq!(
+            roles.grant(accounts.alice, Role::Admin, accounts.bob),
+            Err(RoleError::AdminIsTransferOnly)
+        );
+        assert_eq!(
+            roles.renounce(accounts.alice, Role::Admin),
+            Err(RoleError::AdminIsTransferOnly)
+        );
+        assert!(roles.has_role(Role::Admin, accounts.alice));
+    }
+
+    #[test]
+    fn test_two_step_admin_transfer() {
+        let accounts = accounts();
+        let mut roles = Roles::new(accounts.alice);
+        assert_eq!(
+            roles.propose_admin(accounts.bob, accounts.bob),
+            Err(RoleError::MissingRole)
+        );
+        let _x = roles.propose_admin(accounts.alice, accounts.bob);
+        assert_eq!(roles.admin(), accounts.alice);
+        assert_eq!(
+            roles.accept_admin(accounts.charlie),
+            Err(RoleError::NotPendingAdmin)
+        );
+        assert_eq!(roles.accept_admin(accounts.bob), Ok(accounts.alice));
+        assert_eq!(roles.admin(), accounts.bob);
+        assert_eq!(roles.pending_admin(), None);
+        assert!(!roles.has_role(Role::Admin, accounts.alice));
+    }
+
+    #[test]
+    fn test_adopt_admin_only_without_one() {
+        let accounts = accounts();
+        // what a contract upgraded from storage without roles decodes to.
+        let mut roles = Roles {
+            admin: Lazy::default(),
+            pending_admin: Lazy::default(),
+            members: Mapping::default(),
+        };
+        assert_eq!(roles.admin(), AccountId::from([0; 32]));
+        assert!(!roles.has_role(Role::Admin, AccountId::from([0; 32])));
+        assert!(roles.adopt_admin(accounts.alice));
+        assert!(!roles.adopt_admin(accounts.bob));
+        assert!(roles.has_role(Role::Admin, accounts.alice));
+    }
+}
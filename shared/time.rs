@@ -0,0 +1,156 @@
+//! Time units for the contracts.
+//!
+//! `block_timestamp` counts milliseconds, and so does everything here. A [`Duration`] is a
+//! length of time, like the time an auditor gets for an audit or an extension of it, and a
+//! [`Deadline`] is a point in time something has to happen by. Messages take durations, and
+//! the contracts turn them into deadlines against the current block, so callers never have
+//! to know the chain's clock.
+//!
+//! Both encode exactly like the `u64` timestamps they replace, so stored values keep
+//! decoding the same.
+
+use core::ops::{Add, Div, Mul};
+
+pub type Timestamp = <ink::env::DefaultEnvironment as ink::env::Environment>::Timestamp;
+
+const SECOND: u64 = 1_000;
+const MINUTE: u64 = 60 * SECOND;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+
+#[derive(
+    scale::Decode, scale::Encode, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Duration(u64);
+
+impl Duration {
+    pub const ZERO: Duration = Duration(0);
+
+    pub const fn from_millis(millis: u64) -> Self {
+        Duration(millis)
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Duration(secs * SECOND)
+    }
+
+    pub const fn from_hours(hours: u64) -> Self {
+        Duration(hours * HOUR)
+    }
+
+    pub const fn from_days(days: u64) -> Self {
+        Duration(days * DAY)
+    }
+
+    pub const fn as_millis(self) -> u64 {
+        self.0
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, other: Duration) -> Duration {
+        Duration(self.0.saturating_add(other.0))
+    }
+}
+
+impl Mul<u64> for Duration {
+    type Output = Duration;
+
+    fn mul(self, times: u64) -> Duration {
+        Duration(self.0.saturating_mul(times))
+    }
+}
+
+/// averaging, e.g. of the extensions the arbiters voted for. dividing by zero gives zero.
+impl Div<u64> for Duration {
+    type Output = Duration;
+
+    fn div(self, parts: u64) -> Duration {
+        Duration(self.0.checked_div(parts).unwrap_or_default())
+    }
+}
+
+#[derive(
+    scale::Decode, scale::Encode, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct Deadline(Timestamp);
+
+impl Deadline {
+    pub const fn at(timestamp: Timestamp) -> Self {
+        Deadline(timestamp)
+    }
+
+    /// the deadline `duration` after `now`.
+    pub const fn after(now: Timestamp, duration: Duration) -> Self {
+        Deadline(now.saturating_add(duration.0))
+    }
+
+    pub const fn timestamp(self) -> Timestamp {
+        self.0
+    }
+
+    /// whether the deadline is over at `now`. the deadline itself already counts as over.
+    pub const fn has_passed(self, now: Timestamp) -> bool {
+        self.0 <= now
+    }
+
+    /// how long is left until the deadline, zero once it has passed.
+    pub const fn remaining(self, now: Timestamp) -> Duration {
+        Duration(self.0.saturating_sub(now))
+    }
+
+    pub const fn extended_by(self, duration: Duration) -> Self {
+        Deadline(self.0.saturating_add(duration.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seven_days_are_seven_days_of_block_time() {
+        let week = Duration::from_days(7);
+        assert_eq!(week.as_millis(), 604_800_000);
+        assert_eq!(week, Duration::from_hours(7 * 24));
+        assert_eq!(week, Duration::from_secs(604_800));
+        let deadline = Deadline::after(1_000, week);
+        assert_eq!(deadline.timestamp(), 604_801_000);
+        assert!(!deadline.has_passed(604_800_999));
+        assert!(deadline.has_passed(604_801_000));
+    }
+
+    #[test]
+    fn encodes_like_a_timestamp() {
+        use scale::{Decode, Encode};
+        let deadline = Deadline::at(42);
+        assert_eq!(deadline.encode(), 42u64.encode());
+        assert_eq!(
+            Duration::decode(&mut &42u64.encode()[..]).unwrap(),
+            Duration::from_millis(42)
+        );
+    }
+
+    #[test]
+    fn averaging_by_zero_gives_zero() {
+        assert_eq!(Duration::from_days(3) / 0, Duration::ZERO);
+        assert_eq!(
+            (Duration::from_days(7) + Duration::from_days(15)) / 2,
+            Duration::from_days(11)
+        );
+    }
+}
@@ -1,9 +1,23 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+pub use self::voting::{Voting, VotingRef};
+
 #[ink::contract]
 mod voting {
     use ink::prelude::vec::Vec;
-    use ink::storage::Mapping;
+    use ink::storage::{Lazy, Mapping};
+    use shared::evidence::Evidence;
+    use shared::keys::WrappedKey;
+    use shared::roles::{Role, RoleError, Roles};
+    use shared::time::{Deadline, Duration};
+
+    /// percentage of a poll's treasury that goes to the arbiter provider (the admin),
+    /// the rest is split equally between the arbiters who voted on the poll.
+    pub const PROVIDER_SHARE: Balance = 5;
+    /// the extension an arbiter votes for when finding minor discrepancies.
+    pub const MINOR_DISCREPANCIES_EXTENSION: Duration = Duration::from_days(7);
+    /// the extension an arbiter votes for when finding moderate discrepancies.
+    pub const MODERATE_DISCREPANCIES_EXTENSION: Duration = Duration::from_days(15);
 
     #[derive(scale::Decode, scale::Encode)]
     #[cfg_attr(
@@ -22,7 +36,27 @@ mod voting {
     )]
     ///VoteInfo will store crucial information about the voting
     /// like the vector of arbiters, how many arbiters/voters are there, decided deadline, and haircut will update
+    /// report_version is the version of the audit's report on escrow the arbiters are deciding on.
     pub struct VoteInfo {
+        pub audit_id: u32,
+        pub report_version: u32,
+        pub arbiters: Vec<Arbiter>,
+        pub is_active: bool,
+        pub available_votes: u32,
+        pub decided_deadline: Duration,
+        pub decided_haircut: Balance,
+        pub admin_hit_time: Deadline,
+    }
+
+    #[derive(scale::Decode, scale::Encode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// a poll as it was stored before storage version 1, when the vote count was a `u8`
+    /// and the extensions voted for were summed up in seconds.
+    /// Kept only to read and migrate old entries.
+    pub struct VoteInfoV0 {
         pub audit_id: u32,
         pub arbiters: Vec<Arbiter>,
         pub is_active: bool,
@@ -31,6 +65,27 @@ mod voting {
         pub decided_haircut: Balance,
         pub admin_hit_time: Timestamp,
     }
+
+    impl From<VoteInfoV0> for VoteInfo {
+        fn from(legacy: VoteInfoV0) -> Self {
+            VoteInfo {
+                audit_id: legacy.audit_id,
+                // audits only had the one report then.
+                report_version: 0,
+                arbiters: legacy.arbiters,
+                is_active: legacy.is_active,
+                available_votes: legacy.available_votes.into(),
+                // the extensions used to be counted in seconds.
+                decided_deadline: Duration::from_secs(legacy.decided_deadline),
+                decided_haircut: legacy.decided_haircut,
+                admin_hit_time: Deadline::at(legacy.admin_hit_time),
+            }
+        }
+    }
+
+    /// the layout version the code expects the storage to be in, bumped whenever a
+    /// stored type changes shape and a migration is added for it.
+    pub const STORAGE_VERSION: u32 = 1;
     pub type Result<T> = core::result::Result<T, Error>;
 
     #[derive(scale::Decode, scale::Encode)]
@@ -58,12 +113,74 @@ mod voting {
         vote_type: Option<AuditArbitrationResult>,
     }
 
+    /// emitted when an arbiter votes on whether a bug claim against an audit holds.
+    #[ink(event)]
+    pub struct ClaimVoted {
+        id: u32,
+        voter: AccountId,
+        upheld: bool,
+    }
+
     #[ink(event)]
     pub struct FinalVotePushed {
         id: u32,
         pusher: AccountId,
     }
 
+    /// emitted when escrow credits the arbiters' cut of an audit to a poll's treasury.
+    #[ink(event)]
+    pub struct TreasuryFunded {
+        id: u32,
+        amount: Balance,
+    }
+
+    /// emitted when the admin sweeps surplus tokens out of the contract.
+    #[ink(event)]
+    pub struct TokensSwept {
+        token: AccountId,
+        receiver: AccountId,
+        amount: Balance,
+    }
+
+    /// emitted whenever tokens leave a poll's treasury, either as an arbiter's share
+    /// or as the provider's cut.
+    #[ink(event)]
+    pub struct TreasuryPaidOut {
+        id: u32,
+        receiver: AccountId,
+        amount: Balance,
+    }
+
+    /// emitted when the admin grants a role to an account.
+    #[ink(event)]
+    pub struct RoleGranted {
+        role: Role,
+        account: AccountId,
+        sender: AccountId,
+    }
+
+    /// emitted when a role is revoked by the admin or renounced by its holder.
+    #[ink(event)]
+    pub struct RoleRevoked {
+        role: Role,
+        account: AccountId,
+        sender: AccountId,
+    }
+
+    /// emitted when the admin proposes a successor, who still has to accept.
+    #[ink(event)]
+    pub struct AdminTransferProposed {
+        admin: AccountId,
+        proposed: AccountId,
+    }
+
+    /// emitted when the proposed admin accepts and takes over.
+    #[ink(event)]
+    pub struct AdminTransferred {
+        previous: AccountId,
+        admin: AccountId,
+    }
+
     #[derive(scale::Decode, scale::Encode)]
     #[cfg_attr(
         feature = "std",
@@ -76,33 +193,118 @@ mod voting {
         VotingFailed,
         RightsNotActivatedYet,
         TransferFailed,
+        PollNotFound,
+        PollStillActive,
+        TreasuryEmpty,
+        NoOneVoted,
+        AlreadyClaimed,
+        ExceedsSurplus,
+        AdminIsTransferOnly,
+        UpgradeFailed,
+        WrongPollKind,
+        StablecoinAlreadySet,
+    }
+
+    impl From<RoleError> for Error {
+        fn from(error: RoleError) -> Self {
+            match error {
+                RoleError::AdminIsTransferOnly => Error::AdminIsTransferOnly,
+                RoleError::MissingRole | RoleError::NotPendingAdmin => Error::UnAuthorisedCall,
+            }
+        }
+    }
+
+    /// emitted when the contract's code is swapped out for a new version.
+    #[ink(event)]
+    pub struct CodeUpgraded {
+        code_hash: Hash,
+    }
+
+    /// emitted after each batch of polls is moved to the current storage layout,
+    /// `version` is only bumped once every poll has been migrated.
+    #[ink(event)]
+    pub struct PollsMigrated {
+        migrated: u32,
+        next_id: u32,
+        version: u32,
     }
 
     /// Defines the storage of your contract.
-    /// Add new fields to the below struct in order
-    /// to add new static storage fields to your contract.
+    /// The plain fields are stored packed together, so a field added after deployment
+    /// would make the existing storage undecodable after an upgrade. New fields have
+    /// to be a `Lazy` or a `Mapping`, which live under their own keys.
     #[ink(storage)]
     pub struct Voting {
         pub current_vote_id: u32,
         pub escrow_address: AccountId,
-        pub admin: AccountId,
-        pub vote_id_to_info: Mapping<u32, VoteInfo>,
+        /// the admin the contract was deployed with. `roles` keeps the current one, and only takes
+        /// this one over in `migrate_polls` on a contract deployed before roles existed.
+        admin: AccountId,
+        /// keeps its admin and members in their own storage cells, adding nothing to the root.
+        roles: Roles,
+        /// polls stored before storage version 1, emptied out by `migrate_polls`.
+        pub vote_id_to_info: Mapping<u32, VoteInfoV0>,
+        /// tokens currently held for each poll, credited by escrow and paid out once the poll closes.
+        pub vote_id_to_treasury: Mapping<u32, Balance>,
+        /// what every voting arbiter of a settled poll is owed, fixed when the poll's treasury is settled.
+        pub vote_id_to_voter_share: Mapping<u32, Balance>,
+        pub claimed_shares: Mapping<(u32, AccountId), ()>,
+        /// sum of all the poll treasuries. escrow credits it while re-entering this contract
+        /// from inside `vote`/`force_vote`, so it lives in its own storage cell and isn't
+        /// overwritten when the outer call writes the root storage back.
+        total_treasury: Lazy<Balance>,
+        vote_id_to_info_v1: Mapping<u32, VoteInfo>,
+        storage_version: Lazy<u32>,
+        /// the next poll id `migrate_polls` will look at.
+        migration_cursor: Lazy<u32>,
+        /// the polls deciding on a patron's claim that an audit missed a bug, rather than on its report.
+        claim_polls: Mapping<u32, ()>,
+        /// how many of the arbiters who voted on a claim poll upheld the claim.
+        vote_id_to_upheld_votes: Mapping<u32, u32>,
+        /// the token the poll treasuries are held in.
+        stablecoin_address: Lazy<AccountId>,
     }
 
     impl Voting {
         /// Constructor that initializes the `bool` value to the given `init_value`.
         #[ink(constructor)]
-        pub fn new(_escrow_address: AccountId, _admin: AccountId) -> Self {
+        pub fn new(
+            _escrow_address: AccountId,
+            _stablecoin_address: AccountId,
+            _admin: AccountId,
+        ) -> Self {
             let current_vote_id = u32::default();
             let vote_id_to_info = Mapping::default();
+            let vote_id_to_treasury = Mapping::default();
+            let vote_id_to_voter_share = Mapping::default();
+            let claimed_shares = Mapping::default();
+            let mut total_treasury = Lazy::default();
+            total_treasury.set(&0);
+            let mut storage_version = Lazy::default();
+            storage_version.set(&STORAGE_VERSION);
             let escrow_address = _escrow_address;
-            let admin = _admin;
+            let mut stablecoin_address = Lazy::default();
+            stablecoin_address.set(&_stablecoin_address);
+            // the admin starts out as the arbiter manager too, and can hand that role to others.
+            let mut roles = Roles::new(_admin);
+            let _ = roles.grant(_admin, Role::ArbiterManager, _admin);
 
             Self {
                 current_vote_id,
                 vote_id_to_info,
+                vote_id_to_treasury,
+                vote_id_to_voter_share,
+                claimed_shares,
+                total_treasury,
                 escrow_address,
-                admin,
+                admin: _admin,
+                roles,
+                vote_id_to_info_v1: Mapping::default(),
+                storage_version,
+                migration_cursor: Lazy::default(),
+                claim_polls: Mapping::default(),
+                vote_id_to_upheld_votes: Mapping::default(),
+                stablecoin_address,
             }
         }
 
@@ -118,36 +320,211 @@ mod voting {
 
         #[ink(message)]
         pub fn know_your_admin(&self) -> AccountId {
-            self.admin
+            self.roles.admin()
+        }
+
+        /// know_your_stablecoin returns the token the poll treasuries are held in, none on a contract
+        /// deployed before treasuries existed until the admin sets it.
+        #[ink(message)]
+        pub fn know_your_stablecoin(&self) -> Option<AccountId> {
+            self.stablecoin_address.get()
+        }
+
+        /// set_stablecoin can only be called by the admin, and only on a contract deployed before
+        /// treasuries existed, to give it the token escrow credits the poll treasuries in.
+        #[ink(message)]
+        pub fn set_stablecoin(&mut self, _stablecoin_address: AccountId) -> Result<()> {
+            self.roles.ensure_role(Role::Admin, self.env().caller())?;
+            if self.stablecoin_address.get().is_some() {
+                return Err(Error::StablecoinAlreadySet);
+            }
+            self.stablecoin_address.set(&_stablecoin_address);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_storage_version(&self) -> u32 {
+            self.storage_version.get().unwrap_or_default()
         }
 
         #[ink(message)]
         pub fn get_poll_info(&self, _id: u32) -> Option<VoteInfo> {
-            self.vote_id_to_info.get(&_id)
+            self.load_poll(_id)
+        }
+
+        #[ink(message)]
+        pub fn get_treasury_info(&self, _id: u32) -> Option<Balance> {
+            self.vote_id_to_treasury.get(_id)
         }
 
-        ///create_new_poll can only be called by the admin of this contract, and will be called when patron rejects a submitted report
-        /// the function takes the audit id of the audit under dispute and a list of arbiters who are going to vote on this proposal
+        /// whether the poll decides on a bug claim against an audit, see create_claim_poll.
+        #[ink(message)]
+        pub fn is_claim_poll(&self, _vote_id: u32) -> bool {
+            self.claim_polls.contains(_vote_id)
+        }
+
+        /// get_poll_evidence returns the evidence the patron and the auditor attached to the audit the poll
+        /// decides on, oldest first from start on, as escrow keeps it. an unknown poll has none.
+        #[ink(message)]
+        pub fn get_poll_evidence(&self, _vote_id: u32, start: u32, count: u32) -> Vec<Evidence> {
+            let audit_id = match self.load_poll(_vote_id) {
+                Some(poll) => poll.audit_id,
+                None => return Vec::new(),
+            };
+            ink::env::call::build_call::<Environment>()
+                .call(self.escrow_address)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("get_evidence"),
+                    ))
+                    .push_arg(audit_id)
+                    .push_arg(start)
+                    .push_arg(count),
+                )
+                .returns::<Vec<Evidence>>()
+                .invoke()
+        }
+
+        /// get_poll_report_key returns the content key of the report the poll decides on, wrapped for the
+        /// arbiter, as escrow keeps it. arbiters drawn after the report was submitted get it from the patron
+        /// or another arbiter sharing it on escrow. an unknown poll has none.
+        #[ink(message)]
+        pub fn get_poll_report_key(&self, _vote_id: u32, arbiter: AccountId) -> Option<WrappedKey> {
+            let poll = self.load_poll(_vote_id)?;
+            ink::env::call::build_call::<Environment>()
+                .call(self.escrow_address)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("get_wrapped_key"),
+                    ))
+                    .push_arg(poll.audit_id)
+                    .push_arg(poll.report_version)
+                    .push_arg(arbiter),
+                )
+                .returns::<Option<WrappedKey>>()
+                .invoke()
+        }
+
+        #[ink(message)]
+        pub fn get_total_treasury(&self) -> Balance {
+            self.total_treasury.get().unwrap_or_default()
+        }
+
+        /// verify_treasury checks the sum of all poll treasuries against the contract's actual
+        /// balance on the stablecoin, returning true if every treasury is fully backed.
+        #[ink(message)]
+        pub fn verify_treasury(&self) -> bool {
+            match self.stablecoin_address.get() {
+                Some(stablecoin) => self.holdings_of(stablecoin) >= self.get_total_treasury(),
+                None => self.get_total_treasury() == 0,
+            }
+        }
+
+        /// has_role tells whether the account holds the role, the admin role is held by the admin only.
+        #[ink(message)]
+        pub fn has_role(&self, role: Role, account: AccountId) -> bool {
+            self.roles.has_role(role, account)
+        }
+
+        #[ink(message)]
+        pub fn get_pending_admin(&self) -> Option<AccountId> {
+            self.roles.pending_admin()
+        }
+
+        /// grant_role can only be called by the admin, and gives the role to the account.
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: Role, account: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            if self.roles.grant(caller, role, account)? {
+                self.env().emit_event(RoleGranted {
+                    role,
+                    account,
+                    sender: caller,
+                });
+            }
+            Ok(())
+        }
+
+        /// revoke_role can only be called by the admin, and takes the role away from the account.
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: Role, account: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            if self.roles.revoke(caller, role, account)? {
+                self.env().emit_event(RoleRevoked {
+                    role,
+                    account,
+                    sender: caller,
+                });
+            }
+            Ok(())
+        }
+
+        /// renounce_role lets the caller give up a role it holds.
+        #[ink(message)]
+        pub fn renounce_role(&mut self, role: Role) -> Result<()> {
+            let caller = self.env().caller();
+            if self.roles.renounce(caller, role)? {
+                self.env().emit_event(RoleRevoked {
+                    role,
+                    account: caller,
+                    sender: caller,
+                });
+            }
+            Ok(())
+        }
+
+        /// propose_admin is the first step of handing over the admin role, the proposed account has to
+        /// call accept_admin before anything changes, and a new proposal replaces the previous one.
+        #[ink(message)]
+        pub fn propose_admin(&mut self, new_admin: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            self.roles.propose_admin(caller, new_admin)?;
+            self.env().emit_event(AdminTransferProposed {
+                admin: caller,
+                proposed: new_admin,
+            });
+            Ok(())
+        }
+
+        /// accept_admin is called by the proposed admin to take over from the current one.
+        #[ink(message)]
+        pub fn accept_admin(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let previous = self.roles.accept_admin(caller)?;
+            self.env().emit_event(AdminTransferred {
+                previous,
+                admin: caller,
+            });
+            Ok(())
+        }
+
+        ///create_new_poll can only be called by an arbiter manager of this contract, and will be called when patron rejects a submitted report
+        /// the function takes the audit id of the audit under dispute, the version of its report under dispute as named by
+        /// escrow's AuditRequestsArbitration event, and a list of arbiters who are going to vote on this proposal
         #[ink(message)]
         pub fn create_new_poll(
             &mut self,
             _audit_id: u32,
-            _buffer_for_admin: Timestamp,
+            _report_version: u32,
+            _buffer_for_admin: Duration,
             _arbiters: Vec<Arbiter>,
         ) -> Result<()> {
-            if self.env().caller() != self.admin {
-                return Err(Error::UnAuthorisedCall);
-            }
+            self.roles.ensure_role(Role::ArbiterManager, self.env().caller())?;
             let x = VoteInfo {
                 audit_id: _audit_id,
+                report_version: _report_version,
                 arbiters: _arbiters,
                 is_active: true,
                 available_votes: 0,
-                decided_deadline: 0,
+                decided_deadline: Duration::ZERO,
                 decided_haircut: 0,
-                admin_hit_time: _buffer_for_admin,
+                admin_hit_time: Deadline::after(self.env().block_timestamp(), _buffer_for_admin),
             };
-            self.vote_id_to_info.insert(self.current_vote_id, &x);
+            self.save_poll(self.current_vote_id, &x);
             self.env().emit_event(PollCreated {
                 id: self.current_vote_id,
                 vote_info: x,
@@ -156,6 +533,64 @@ mod voting {
             return Ok(());
         }
 
+        /// create_claim_poll can only be called by an arbiter manager of this contract, and will be called when the patron
+        /// of a completed audit claims on escrow, with BugClaimFiled, that the audit missed a critical bug in its scope.
+        /// the arbiters vote on the claim with vote_on_claim instead of vote, and the report version is the one accepted.
+        #[ink(message)]
+        pub fn create_claim_poll(
+            &mut self,
+            _audit_id: u32,
+            _report_version: u32,
+            _buffer_for_admin: Duration,
+            _arbiters: Vec<Arbiter>,
+        ) -> Result<()> {
+            let vote_id = self.current_vote_id;
+            self.create_new_poll(_audit_id, _report_version, _buffer_for_admin, _arbiters)?;
+            self.claim_polls.insert(vote_id, &());
+            Ok(())
+        }
+
+        /// vote_on_claim lets an arbiter of a claim poll vote on whether the claim holds. once every arbiter has voted,
+        /// escrow is told whether most of them upheld it, a tie leaving the auditor's payout with them.
+        #[ink(message)]
+        pub fn vote_on_claim(&mut self, _vote_id: u32, _upheld: bool) -> Result<()> {
+            let mut x = self.load_poll(_vote_id).ok_or(Error::PollNotFound)?;
+            if !self.is_claim_poll(_vote_id) {
+                return Err(Error::WrongPollKind);
+            }
+            if !x.is_active {
+                return Err(Error::ResultAlreadyPublished);
+            }
+            let caller = self.env().caller();
+            let arbiter = x
+                .arbiters
+                .iter_mut()
+                .find(|arbiter| arbiter.voter_address == caller)
+                .ok_or(Error::UnAuthorisedCall)?;
+            if arbiter.has_voted {
+                return Err(Error::VotingFailed);
+            }
+            arbiter.has_voted = true;
+            x.available_votes += 1;
+            if _upheld {
+                let upheld = self
+                    .vote_id_to_upheld_votes
+                    .get(_vote_id)
+                    .unwrap_or_default();
+                self.vote_id_to_upheld_votes.insert(_vote_id, &(upheld + 1));
+            }
+            self.env().emit_event(ClaimVoted {
+                id: _vote_id,
+                voter: caller,
+                upheld: _upheld,
+            });
+            if x.available_votes == x.arbiters.len() as u32 {
+                return self.resolve_claim(_vote_id, x);
+            }
+            self.save_poll(_vote_id, &x);
+            Ok(())
+        }
+
         /// vote function is the main function of this contract, taking in vote_id and result as input by the arbiters,
         /// it first verifies that the voting is still active, and that the arbiter hasn't already voted.
         /// then it updates the state of this and the other contract according to stage.
@@ -164,7 +599,10 @@ mod voting {
         /// But otherwise it will simply be compounded into decided_deadline and decided_haircut to be averaged out eventually.
         #[ink(message)]
         pub fn vote(&mut self, _vote_id: u32, _result: AuditArbitrationResult) -> Result<()> {
-            let mut x = self.vote_id_to_info.get(_vote_id).unwrap();
+            if self.is_claim_poll(_vote_id) {
+                return Err(Error::WrongPollKind);
+            }
+            let mut x = self.load_poll(_vote_id).ok_or(Error::PollNotFound)?;
             if !x.is_active {
                 return Err(Error::ResultAlreadyPublished);
             }
@@ -182,16 +620,17 @@ mod voting {
                     return Err(Error::VotingFailed);
                 } else {
                     //case when this is the last vote to be done... submit thing..
-                    if x.available_votes + 1 == x.arbiters.len() as u8 {
+                    if x.available_votes + 1 == x.arbiters.len() as u32 {
                         match _result {
                             AuditArbitrationResult::NoDiscrepancies => {
-                                if x.decided_deadline > 0 {
+                                if !x.decided_deadline.is_zero() {
                                     x.decided_deadline =
-                                        (x.decided_deadline) / (x.available_votes as Timestamp + 1);
+                                        (x.decided_deadline) / (u64::from(x.available_votes) + 1);
                                     x.decided_haircut =
                                         (x.decided_haircut) / (x.available_votes as Balance + 1);
                                     let result_call = ink::env::call::build_call::<Environment>()
                                         .call(self.escrow_address)
+                                        .call_flags(ink::env::CallFlags::default().set_allow_reentry(true))
                                         .gas_limit(0)
                                         .transferred_value(0)
                                         .exec_input(
@@ -214,7 +653,7 @@ mod voting {
                                         x.is_active = false;
                                         x.available_votes = x.available_votes + 1;
                                         x.arbiters[index].has_voted = true;
-                                        self.vote_id_to_info.insert(_vote_id, &x);
+                                        self.save_poll(_vote_id, &x);
                                         self.env().emit_event(ArbiterVoted {
                                             id: _vote_id,
                                             voter: self.env().caller(),
@@ -231,6 +670,7 @@ mod voting {
                                 } else {
                                     let result_call = ink::env::call::build_call::<Environment>()
                                         .call(self.escrow_address)
+                                        .call_flags(ink::env::CallFlags::default().set_allow_reentry(true))
                                         .gas_limit(0)
                                         .transferred_value(0)
                                         .exec_input(
@@ -249,7 +689,7 @@ mod voting {
                                         x.available_votes = x.available_votes + 1;
                                         x.arbiters[index].has_voted = true;
                                         x.is_active = false;
-                                        self.vote_id_to_info.insert(_vote_id, &x);
+                                        self.save_poll(_vote_id, &x);
                                         return Ok(());
                                     } else {
                                         return Err(Error::AssessmentFailed);
@@ -258,12 +698,14 @@ mod voting {
                             }
                             AuditArbitrationResult::MinorDiscrepancies => {
                                 //add 7 days to the deadline extension.
-                                x.decided_deadline = (x.decided_deadline + 604800)
-                                    / (x.available_votes as Timestamp + 1);
+                                x.decided_deadline = (x.decided_deadline
+                                    + MINOR_DISCREPANCIES_EXTENSION)
+                                    / (u64::from(x.available_votes) + 1);
                                 x.decided_haircut =
                                     (x.decided_haircut + 5) / (x.available_votes as Balance + 1);
                                 let result_call = ink::env::call::build_call::<Environment>()
                                     .call(self.escrow_address)
+                                    .call_flags(ink::env::CallFlags::default().set_allow_reentry(true))
                                     .gas_limit(0)
                                     .transferred_value(0)
                                     .exec_input(
@@ -284,7 +726,7 @@ mod voting {
                                     x.available_votes = x.available_votes + 1;
                                     x.arbiters[index].has_voted = true;
                                     x.is_active = false;
-                                    self.vote_id_to_info.insert(_vote_id, &x);
+                                    self.save_poll(_vote_id, &x);
                                     self.env().emit_event(ArbiterVoted {
                                         id: _vote_id,
                                         voter: self.env().caller(),
@@ -301,12 +743,14 @@ mod voting {
                             }
                             AuditArbitrationResult::ModerateDiscrepancies => {
                                 //add 15 days to the deadline extension.
-                                x.decided_deadline = (x.decided_deadline + 1209600)
-                                    / (x.available_votes as Timestamp + 1);
+                                x.decided_deadline = (x.decided_deadline
+                                    + MODERATE_DISCREPANCIES_EXTENSION)
+                                    / (u64::from(x.available_votes) + 1);
                                 x.decided_haircut =
                                     (x.decided_haircut + 15) / (x.available_votes as Balance + 1);
                                 let result_call = ink::env::call::build_call::<Environment>()
                                     .call(self.escrow_address)
+                                    .call_flags(ink::env::CallFlags::default().set_allow_reentry(true))
                                     .gas_limit(0)
                                     .transferred_value(0)
                                     .exec_input(
@@ -327,7 +771,7 @@ mod voting {
                                     x.available_votes = x.available_votes + 1;
                                     x.arbiters[index].has_voted = true;
                                     x.is_active = false;
-                                    self.vote_id_to_info.insert(_vote_id, &x);
+                                    self.save_poll(_vote_id, &x);
                                     self.env().emit_event(ArbiterVoted {
                                         id: _vote_id,
                                         voter: self.env().caller(),
@@ -346,6 +790,7 @@ mod voting {
                                 //call the function that rejects the audit report.
                                 let result_call = ink::env::call::build_call::<Environment>()
                                     .call(self.escrow_address)
+                                    .call_flags(ink::env::CallFlags::default().set_allow_reentry(true))
                                     .gas_limit(0)
                                     .transferred_value(0)
                                     .exec_input(
@@ -364,7 +809,7 @@ mod voting {
                                     x.available_votes = x.available_votes + 1;
                                     x.arbiters[index].has_voted = true;
                                     x.is_active = false;
-                                    self.vote_id_to_info.insert(_vote_id, &x);
+                                    self.save_poll(_vote_id, &x);
                                     self.env().emit_event(ArbiterVoted {
                                         id: _vote_id,
                                         voter: self.env().caller(),
@@ -385,7 +830,7 @@ mod voting {
                             AuditArbitrationResult::NoDiscrepancies => {
                                 x.available_votes = x.available_votes + 1;
                                 x.arbiters[index].has_voted = true;
-                                self.vote_id_to_info.insert(_vote_id, &x);
+                                self.save_poll(_vote_id, &x);
                                 self.env().emit_event(ArbiterVoted {
                                     id: _vote_id,
                                     voter: self.env().caller(),
@@ -397,9 +842,10 @@ mod voting {
                                 x.available_votes = x.available_votes + 1;
                                 x.arbiters[index].has_voted = true;
                                 //add 7 days to the deadline extension.
-                                x.decided_deadline = x.decided_deadline + 604800;
+                                x.decided_deadline =
+                                    x.decided_deadline + MINOR_DISCREPANCIES_EXTENSION;
                                 x.decided_haircut = x.decided_haircut + 5;
-                                self.vote_id_to_info.insert(_vote_id, &x);
+                                self.save_poll(_vote_id, &x);
                                 self.env().emit_event(ArbiterVoted {
                                     id: _vote_id,
                                     voter: self.env().caller(),
@@ -411,9 +857,10 @@ mod voting {
                                 x.available_votes = x.available_votes + 1;
                                 x.arbiters[index].has_voted = true;
                                 //add 15 days to the deadline extension.
-                                x.decided_deadline = x.decided_deadline + 1209600;
+                                x.decided_deadline =
+                                    x.decided_deadline + MODERATE_DISCREPANCIES_EXTENSION;
                                 x.decided_haircut = x.decided_haircut + 15;
-                                self.vote_id_to_info.insert(_vote_id, &x);
+                                self.save_poll(_vote_id, &x);
                                 self.env().emit_event(ArbiterVoted {
                                     id: _vote_id,
                                     voter: self.env().caller(),
@@ -424,6 +871,7 @@ mod voting {
                             AuditArbitrationResult::Reject => {
                                 let result_call = ink::env::call::build_call::<Environment>()
                                     .call(self.escrow_address)
+                                    .call_flags(ink::env::CallFlags::default().set_allow_reentry(true))
                                     .gas_limit(0)
                                     .transferred_value(0)
                                     .exec_input(
@@ -442,7 +890,7 @@ mod voting {
                                     x.available_votes = x.available_votes + 1;
                                     x.arbiters[index].has_voted = true;
                                     x.is_active = false;
-                                    self.vote_id_to_info.insert(_vote_id, &x);
+                                    self.save_poll(_vote_id, &x);
                                     self.env().emit_event(ArbiterVoted {
                                         id: _vote_id,
                                         voter: self.env().caller(),
@@ -463,26 +911,33 @@ mod voting {
             }
         }
 
-        ///In case when not all arbiters have voted on a particular proposal, the admin has the liberty of forcing the vote by submitting the
+        ///In case when not all arbiters have voted on a particular proposal, an arbiter manager has the liberty of forcing the vote by submitting the
         /// current decision, accordingly it will either approve the auditor or extend their deadline.
         #[ink(message)]
         pub fn force_vote(&mut self, _vote_id: u32) -> Result<()> {
-            if self.env().caller() != self.admin {
-                return Err(Error::UnAuthorisedCall);
-            }
-            if self.vote_id_to_info.get(_vote_id).unwrap().admin_hit_time
-                > self.env().block_timestamp()
-            {
+            self.roles.ensure_role(Role::ArbiterManager, self.env().caller())?;
+            let mut x = self.load_poll(_vote_id).ok_or(Error::PollNotFound)?;
+            if !x.admin_hit_time.has_passed(self.env().block_timestamp()) {
                 return Err(Error::RightsNotActivatedYet);
             }
-            let mut x = self.vote_id_to_info.get(_vote_id).unwrap();
 
             if !x.is_active {
                 return Err(Error::ResultAlreadyPublished);
             }
-            if x.decided_deadline > 0 {
+            // a claim is decided by the arbiters who voted on it so far.
+            if self.is_claim_poll(_vote_id) {
+                if x.available_votes == 0 {
+                    return Err(Error::NoOneVoted);
+                }
+                return self.resolve_claim(_vote_id, x);
+            }
+            if !x.decided_deadline.is_zero() {
+                // escrow gets the average of what the arbiters who voted asked for.
+                x.decided_deadline = x.decided_deadline / u64::from(x.available_votes);
+                x.decided_haircut /= x.available_votes as Balance;
                 let result_call = ink::env::call::build_call::<Environment>()
                     .call(self.escrow_address)
+                    .call_flags(ink::env::CallFlags::default().set_allow_reentry(true))
                     .gas_limit(0)
                     .transferred_value(0)
                     .exec_input(
@@ -490,7 +945,7 @@ mod voting {
                             ink::selector_bytes!("arbiters_extend_deadline"),
                         ))
                         .push_arg(&x.audit_id)
-                        .push_arg(&x.decided_deadline + self.env().block_timestamp())
+                        .push_arg(&x.decided_deadline)
                         .push_arg(&x.decided_haircut)
                         .push_arg(5)
                         .push_arg(_vote_id),
@@ -499,9 +954,7 @@ mod voting {
                     .try_invoke();
                 if matches!(result_call.unwrap().unwrap(), Result::Ok(())) {
                     x.is_active = false;
-                    x.decided_deadline = (x.decided_deadline) / (x.available_votes as Timestamp);
-                    x.decided_haircut = (x.decided_haircut) / (x.available_votes as Balance);
-                    self.vote_id_to_info.insert(_vote_id, &x);
+                    self.save_poll(_vote_id, &x);
                     self.env().emit_event(FinalVotePushed {
                         id: _vote_id,
                         pusher: self.env().caller(),
@@ -513,6 +966,7 @@ mod voting {
             } else {
                 let result_call = ink::env::call::build_call::<Environment>()
                     .call(self.escrow_address)
+                    .call_flags(ink::env::CallFlags::default().set_allow_reentry(true))
                     .gas_limit(0)
                     .transferred_value(0)
                     .exec_input(
@@ -527,9 +981,9 @@ mod voting {
                     .try_invoke();
                 if matches!(result_call.unwrap().unwrap(), Result::Ok(())) {
                     x.is_active = false;
-                    x.decided_deadline = (x.decided_deadline) / (x.available_votes as Timestamp);
+                    x.decided_deadline = (x.decided_deadline) / u64::from(x.available_votes);
                     x.decided_haircut = (x.decided_haircut) / (x.available_votes as Balance);
-                    self.vote_id_to_info.insert(_vote_id, &x);
+                    self.save_poll(_vote_id, &x);
                     return Ok(());
                 } else {
                     return Err(Error::AssessmentFailed);
@@ -537,12 +991,229 @@ mod voting {
             }
         }
 
+        /// add_to_treasury can only be called by the escrow contract, right after it has transferred the arbiters'
+        /// cut of an audit to this contract. the amount is credited to the poll that decided on the audit, and the
+        /// poll has to still be active, since escrow is only ever paid out from within the final vote.
+        #[ink(message)]
+        pub fn add_to_treasury(&mut self, _vote_id: u32, funds: Balance) -> Result<()> {
+            if self.env().caller() != self.escrow_address {
+                return Err(Error::UnAuthorisedCall);
+            }
+            let vote_info = self.load_poll(_vote_id).ok_or(Error::PollNotFound)?;
+            if !vote_info.is_active {
+                return Err(Error::ResultAlreadyPublished);
+            }
+            let treasury = self.vote_id_to_treasury.get(_vote_id).unwrap_or_default();
+            self.vote_id_to_treasury.insert(_vote_id, &(treasury + funds));
+            self.total_treasury.set(&(self.get_total_treasury() + funds));
+            self.env().emit_event(TreasuryFunded {
+                id: _vote_id,
+                amount: funds,
+            });
+            Ok(())
+        }
+
+        /// claim_arbiter_share lets an arbiter who voted on a closed poll pull their share of its treasury.
+        #[ink(message)]
+        pub fn claim_arbiter_share(&mut self, _vote_id: u32) -> Result<()> {
+            let caller = self.env().caller();
+            let vote_info = self.load_poll(_vote_id).ok_or(Error::PollNotFound)?;
+            if !vote_info
+                .arbiters
+                .iter()
+                .any(|arbiter| arbiter.voter_address == caller && arbiter.has_voted)
+            {
+                return Err(Error::UnAuthorisedCall);
+            }
+            if self.claimed_shares.contains((_vote_id, caller)) {
+                return Err(Error::AlreadyClaimed);
+            }
+            let share = self.settle_treasury(_vote_id, &vote_info)?;
+            self.pay_from_treasury(_vote_id, caller, share)?;
+            self.claimed_shares.insert((_vote_id, caller), &());
+            Ok(())
+        }
+
+        /// release_treasury_funds is called by the admin once a poll is closed, and pays every arbiter who voted
+        /// and hasn't claimed yet their share of the poll's treasury.
+        #[ink(message)]
+        pub fn release_treasury_funds(&mut self, _vote_id: u32) -> Result<()> {
+            self.roles.ensure_role(Role::Admin, self.env().caller())?;
+            let vote_info = self.load_poll(_vote_id).ok_or(Error::PollNotFound)?;
+            let share = self.settle_treasury(_vote_id, &vote_info)?;
+            for arbiter in vote_info.arbiters.iter().filter(|arbiter| arbiter.has_voted) {
+                if self.claimed_shares.contains((_vote_id, arbiter.voter_address)) {
+                    continue;
+                }
+                self.pay_from_treasury(_vote_id, arbiter.voter_address, share)?;
+                self.claimed_shares.insert((_vote_id, arbiter.voter_address), &());
+            }
+            Ok(())
+        }
+
+        /// upgrade is called by the admin to switch the contract to already uploaded code. The storage
+        /// stays as it is, and any stored type that changed is brought over with `migrate_polls` afterwards.
+        #[ink(message)]
+        pub fn upgrade(&mut self, code_hash: Hash) -> Result<()> {
+            self.roles.ensure_role(Role::Admin, self.env().caller())?;
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| Error::UpgradeFailed)?;
+            self.env().emit_event(CodeUpgraded { code_hash });
+            Ok(())
+        }
+
+        /// migrate_polls is called by the admin to rewrite up to `count` polls still stored in the old
+        /// layout, picking up where the previous call stopped. Once every poll has been seen the storage
+        /// version is set to `STORAGE_VERSION`.
+        #[ink(message)]
+        pub fn migrate_polls(&mut self, count: u32) -> Result<u32> {
+            // a contract deployed before roles existed only has the admin it was deployed with.
+            self.roles.adopt_admin(self.admin);
+            self.roles.ensure_role(Role::Admin, self.env().caller())?;
+            let start = self.migration_cursor.get().unwrap_or_default();
+            let end = start.saturating_add(count).min(self.current_vote_id);
+            let mut migrated = 0;
+            for id in start..end {
+                if let Some(legacy) = self.vote_id_to_info.take(id) {
+                    self.vote_id_to_info_v1.insert(id, &VoteInfo::from(legacy));
+                    migrated += 1;
+                }
+            }
+            self.migration_cursor.set(&end);
+            if end == self.current_vote_id {
+                self.storage_version.set(&STORAGE_VERSION);
+            }
+            self.env().emit_event(PollsMigrated {
+                migrated,
+                next_id: end,
+                version: self.get_storage_version(),
+            });
+            Ok(migrated)
+        }
+
+        /// tells escrow whether most of the arbiters who voted on the claim upheld it, closing the poll.
+        fn resolve_claim(&mut self, _vote_id: u32, mut x: VoteInfo) -> Result<()> {
+            let upheld_votes = self
+                .vote_id_to_upheld_votes
+                .get(_vote_id)
+                .unwrap_or_default();
+            let upheld = upheld_votes * 2 > x.available_votes;
+            let result_call = ink::env::call::build_call::<Environment>()
+                .call(self.escrow_address)
+                .call_flags(ink::env::CallFlags::default().set_allow_reentry(true))
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("resolve_bug_claim"),
+                    ))
+                    .push_arg(&x.audit_id)
+                    .push_arg(upheld)
+                    .push_arg(_vote_id),
+                )
+                .returns::<Result<()>>()
+                .try_invoke();
+            if !matches!(result_call, Ok(Ok(Ok(())))) {
+                return Err(Error::AssessmentFailed);
+            }
+            x.is_active = false;
+            self.save_poll(_vote_id, &x);
+            self.env().emit_event(FinalVotePushed {
+                id: _vote_id,
+                pusher: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        /// reads a poll in the current layout, falling back to one that hasn't been migrated yet.
+        fn load_poll(&self, _vote_id: u32) -> Option<VoteInfo> {
+            self.vote_id_to_info_v1
+                .get(_vote_id)
+                .or_else(|| self.vote_id_to_info.get(_vote_id).map(VoteInfo::from))
+        }
+
+        /// writes a poll in the current layout, dropping its old entry if there was one.
+        fn save_poll(&mut self, _vote_id: u32, vote_info: &VoteInfo) {
+            self.vote_id_to_info_v1.insert(_vote_id, vote_info);
+            self.vote_id_to_info.remove(_vote_id);
+        }
+
+        /// the first payout from a closed poll settles its treasury: the provider's cut, along with whatever
+        /// doesn't divide evenly between the voters, goes to the admin, and the share of every voter is fixed.
+        fn settle_treasury(&mut self, _vote_id: u32, vote_info: &VoteInfo) -> Result<Balance> {
+            if let Some(share) = self.vote_id_to_voter_share.get(_vote_id) {
+                return Ok(share);
+            }
+            if vote_info.is_active {
+                return Err(Error::PollStillActive);
+            }
+            let funds = self.vote_id_to_treasury.get(_vote_id).unwrap_or_default();
+            if funds == 0 {
+                return Err(Error::TreasuryEmpty);
+            }
+            if vote_info.available_votes == 0 {
+                return Err(Error::NoOneVoted);
+            }
+            let voters = vote_info.available_votes as Balance;
+            let share = (funds - funds * PROVIDER_SHARE / 100) / voters;
+            let provider_cut = funds - share * voters;
+            if provider_cut > 0 {
+                self.pay_from_treasury(_vote_id, self.roles.admin(), provider_cut)?;
+            }
+            self.vote_id_to_voter_share.insert(_vote_id, &share);
+            Ok(share)
+        }
+
+        fn pay_from_treasury(
+            &mut self,
+            _vote_id: u32,
+            receiver: AccountId,
+            amount: Balance,
+        ) -> Result<()> {
+            let treasury = self.vote_id_to_treasury.get(_vote_id).unwrap_or_default();
+            if amount > treasury {
+                return Err(Error::TreasuryEmpty);
+            }
+            let stablecoin = self.stablecoin_address.get().ok_or(Error::TransferFailed)?;
+            let result_call = ink::env::call::build_call::<Environment>()
+                .call(stablecoin)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("transfer"),
+                    ))
+                    .push_arg(receiver)
+                    .push_arg(amount),
+                )
+                .returns::<Result<()>>()
+                .try_invoke();
+            if !matches!(result_call, Ok(Ok(Ok(())))) {
+                return Err(Error::TransferFailed);
+            }
+            self.vote_id_to_treasury.insert(_vote_id, &(treasury - amount));
+            self.total_treasury.set(&(self.get_total_treasury() - amount));
+            self.env().emit_event(TreasuryPaidOut {
+                id: _vote_id,
+                receiver,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// flush_out_tokens lets the admin recover tokens that ended up in this contract by mistake. only the surplus
+        /// above what the contract owes can be swept, so funds sitting in poll treasuries are never touched.
         #[ink(message)]
         pub fn flush_out_tokens(
             &mut self,
             _token_address: AccountId,
             _value: Balance,
         ) -> Result<()> {
+            self.roles.ensure_role(Role::Admin, self.env().caller())?;
+            if _value > self.sweepable_surplus(_token_address) {
+                return Err(Error::ExceedsSurplus);
+            }
             let _result_call = ink::env::call::build_call::<Environment>()
                 .call(_token_address)
                 .gas_limit(0)
@@ -551,16 +1222,340 @@ mod voting {
                     ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
                         ink::selector_bytes!("transfer"),
                     ))
-                    .push_arg(&self.admin)
+                    .push_arg(self.roles.admin())
                     .push_arg(_value),
                 )
                 .returns::<Result<()>>()
                 .try_invoke();
-            if matches!(_result_call.unwrap().unwrap(), Result::Ok(())) {
-                return Ok(());
-            } else {
+            if !matches!(_result_call, Ok(Ok(Ok(())))) {
                 return Err(Error::TransferFailed);
             }
+            self.env().emit_event(TokensSwept {
+                token: _token_address,
+                receiver: self.roles.admin(),
+                amount: _value,
+            });
+            Ok(())
         }
+
+        /// sweepable_surplus returns how much of a token the contract holds above its obligations. only the
+        /// stablecoin can be owed, through the poll treasuries, any other token is surplus in full.
+        #[ink(message)]
+        pub fn sweepable_surplus(&self, _token_address: AccountId) -> Balance {
+            let holdings = self.holdings_of(_token_address);
+            if self.stablecoin_address.get() == Some(_token_address) {
+                holdings.saturating_sub(self.get_total_treasury())
+            } else {
+                holdings
+            }
+        }
+
+        fn holdings_of(&self, _token_address: AccountId) -> Balance {
+            ink::env::call::build_call::<Environment>()
+                .call(_token_address)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ink::env::call::ExecutionInput::new(ink::env::call::Selector::new(
+                        ink::selector_bytes!("balance_of"),
+                    ))
+                    .push_arg(self.env().account_id()),
+                )
+                .returns::<Balance>()
+                .invoke()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_cases {
+    use super::*;
+    use shared::time::Duration;
+
+    fn poll_with_arbiters(
+        contract: &mut voting::Voting,
+        arbiters: ink::prelude::vec::Vec<voting::Arbiter>,
+    ) {
+        let _x = contract.create_new_poll(1, 0, Duration::from_millis(100000000000), arbiters);
+    }
+
+    #[test]
+    fn test_add_to_treasury_by_non_escrow_fails() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        poll_with_arbiters(&mut contract, ink::prelude::vec::Vec::new());
+        let x = contract.add_to_treasury(0, 100);
+        assert!(matches!(x, Err(voting::Error::UnAuthorisedCall)));
+        assert_eq!(contract.get_total_treasury(), 0);
+    }
+
+    #[test]
+    fn test_add_to_treasury_credits_the_poll() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        poll_with_arbiters(&mut contract, ink::prelude::vec::Vec::new());
+        poll_with_arbiters(&mut contract, ink::prelude::vec::Vec::new());
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        let _x = contract.add_to_treasury(0, 100);
+        let _y = contract.add_to_treasury(0, 50);
+        let _z = contract.add_to_treasury(1, 10);
+        assert_eq!(contract.get_treasury_info(0), Some(150));
+        assert_eq!(contract.get_treasury_info(1), Some(10));
+        assert_eq!(contract.get_total_treasury(), 160);
+    }
+
+    #[test]
+    fn test_add_to_treasury_of_unknown_poll_fails() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let x = contract.add_to_treasury(3, 100);
+        assert!(matches!(x, Err(voting::Error::PollNotFound)));
+    }
+
+    #[test]
+    fn test_vote_on_unknown_poll_fails() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let x = contract.vote(3, voting::AuditArbitrationResult::NoDiscrepancies);
+        assert!(matches!(x, Err(voting::Error::PollNotFound)));
+        let _y = contract.grant_role(shared::roles::Role::ArbiterManager, accounts.alice);
+        let z = contract.force_vote(3);
+        assert!(matches!(z, Err(voting::Error::PollNotFound)));
+    }
+
+    #[test]
+    fn test_claim_share_while_poll_active_fails() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = ink::prelude::vec![voting::Arbiter {
+            voter_address: accounts.bob,
+            has_voted: true,
+        }];
+        poll_with_arbiters(&mut contract, arbiters);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+        let _x = contract.add_to_treasury(0, 100);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let y = contract.claim_arbiter_share(0);
+        assert!(matches!(y, Err(voting::Error::PollStillActive)));
+    }
+
+    #[test]
+    fn test_claim_share_by_non_voter_fails() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let arbiters = ink::prelude::vec![voting::Arbiter {
+            voter_address: accounts.bob,
+            has_voted: false,
+        }];
+        poll_with_arbiters(&mut contract, arbiters);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let y = contract.claim_arbiter_share(0);
+        assert!(matches!(y, Err(voting::Error::UnAuthorisedCall)));
+    }
+
+    #[test]
+    fn test_flush_out_tokens_by_non_admin_fails() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let x = contract.flush_out_tokens(accounts.django, 100);
+        assert!(matches!(x, Err(voting::Error::UnAuthorisedCall)));
+    }
+
+    #[test]
+    fn test_create_poll_needs_arbiter_manager() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let buffer = Duration::from_millis(100000000000);
+        let x = contract.create_new_poll(1, 0, buffer, ink::prelude::vec::Vec::new());
+        assert!(matches!(x, Err(voting::Error::UnAuthorisedCall)));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let _y = contract.grant_role(shared::roles::Role::ArbiterManager, accounts.bob);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let z = contract.create_new_poll(1, 0, buffer, ink::prelude::vec::Vec::new());
+        assert!(z.is_ok());
+    }
+
+    #[test]
+    fn test_migrate_polls_converts_legacy_entries() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let legacy = voting::VoteInfoV0 {
+            audit_id: 7,
+            arbiters: ink::prelude::vec::Vec::new(),
+            is_active: true,
+            available_votes: 3,
+            decided_deadline: 0,
+            decided_haircut: 0,
+            admin_hit_time: 0,
+        };
+        contract.vote_id_to_info.insert(0, &legacy);
+        contract.vote_id_to_info.insert(1, &legacy);
+        contract.current_vote_id = 2;
+        assert_eq!(contract.get_poll_info(1).unwrap().available_votes, 3);
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(matches!(contract.migrate_polls(1), Err(voting::Error::UnAuthorisedCall)));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(contract.migrate_polls(1), Ok(1)));
+        assert!(contract.vote_id_to_info.get(0).is_none());
+        assert!(contract.vote_id_to_info.get(1).is_some());
+        assert!(matches!(contract.migrate_polls(5), Ok(1)));
+        assert!(contract.vote_id_to_info.get(1).is_none());
+        assert_eq!(contract.get_poll_info(1).unwrap().audit_id, 7);
+        assert_eq!(contract.get_storage_version(), voting::STORAGE_VERSION);
+    }
+
+    #[test]
+    fn test_admin_buffer_of_seven_days_lasts_seven_days() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000);
+        let x = contract.create_new_poll(1, 0, Duration::from_days(7), ink::prelude::vec::Vec::new());
+        assert!(x.is_ok());
+        let info = contract.get_poll_info(0).unwrap();
+        assert_eq!(info.admin_hit_time.timestamp(), 1000 + 604_800_000);
+        ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(1000 + 604_799_999);
+        let y = contract.force_vote(0);
+        assert!(matches!(y, Err(voting::Error::RightsNotActivatedYet)));
+    }
+
+    #[test]
+    fn test_legacy_extensions_in_seconds_are_converted() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let legacy = voting::VoteInfoV0 {
+            audit_id: 1,
+            arbiters: ink::prelude::vec::Vec::new(),
+            is_active: true,
+            available_votes: 1,
+            decided_deadline: 604800,
+            decided_haircut: 5,
+            admin_hit_time: 0,
+        };
+        contract.vote_id_to_info.insert(0, &legacy);
+        let info = contract.get_poll_info(0).unwrap();
+        assert_eq!(info.decided_deadline, voting::MINOR_DISCREPANCIES_EXTENSION);
+    }
+
+    #[test]
+    fn test_unknown_poll_has_no_evidence() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        assert!(contract.get_poll_evidence(0, 0, 10).is_empty());
+        assert!(contract.get_poll_report_key(0, accounts.bob).is_none());
+    }
+
+    #[test]
+    fn test_claim_polls_take_claim_votes_only() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let mut contract = voting::Voting::new(accounts.charlie, accounts.django, accounts.alice);
+        let (bob, frank) = (accounts.bob, accounts.frank);
+        let arbiters = || {
+            ink::prelude::vec![
+                voting::Arbiter {
+                    voter_address: bob,
+                    has_voted: false,
+                },
+                voting::Arbiter {
+                    voter_address: frank,
+                    has_voted: false,
+                },
+            ]
+        };
+        poll_with_arbiters(&mut contract, arbiters());
+        let buffer = Duration::from_millis(100000000000);
+        assert!(contract.create_claim_poll(1, 0, buffer, arbiters()).is_ok());
+        assert!(!contract.is_claim_poll(0));
+        assert!(contract.is_claim_poll(1));
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        let x = contract.vote_on_claim(0, true);
+        assert!(matches!(x, Err(voting::Error::WrongPollKind)));
+        let y = contract.vote(1, voting::AuditArbitrationResult::Reject);
+        assert!(matches!(y, Err(voting::Error::WrongPollKind)));
+        assert!(contract.vote_on_claim(1, true).is_ok());
+        let z = contract.vote_on_claim(1, false);
+        assert!(matches!(z, Err(voting::Error::VotingFailed)));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+        let w = contract.vote_on_claim(1, false);
+        assert!(matches!(w, Err(voting::Error::UnAuthorisedCall)));
+        let v = contract.create_claim_poll(1, 0, buffer, arbiters());
+        assert!(matches!(v, Err(voting::Error::UnAuthorisedCall)));
+        assert!(!contract.is_claim_poll(2));
+        let info = contract.get_poll_info(1).unwrap();
+        assert_eq!(info.available_votes, 1);
+        assert!(info.is_active);
+    }
+
+    #[test]
+    fn test_baseline_root_still_loads() {
+        let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.eve);
+        let root = <voting::Voting as ink::storage::traits::StorageKey>::KEY;
+        // the root as the first deployment stored it, current_vote_id, escrow_address then admin.
+        ink::env::set_contract_storage(&root, &(1u32, accounts.charlie, accounts.alice));
+        let mut contract = ink::env::get_contract_storage::<_, voting::Voting>(&root)
+            .unwrap()
+            .unwrap();
+        assert_eq!(contract.get_current_vote_id(), 1);
+        assert_eq!(contract.know_your_escrow(), accounts.charlie);
+        assert_eq!(contract.know_your_stablecoin(), None);
+        let legacy = voting::VoteInfoV0 {
+            audit_id: 7,
+            arbiters: ink::prelude::vec::Vec::new(),
+            is_active: true,
+            available_votes: 3,
+            decided_deadline: 0,
+            decided_haircut: 0,
+            admin_hit_time: 0,
+        };
+        contract.vote_id_to_info.insert(0, &legacy);
+        assert_eq!(contract.get_poll_info(0).unwrap().audit_id, 7);
+
+        // the admin it was deployed with takes over the roles on the first migration.
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(matches!(contract.migrate_polls(1), Err(voting::Error::UnAuthorisedCall)));
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        assert!(matches!(contract.migrate_polls(1), Ok(1)));
+        assert_eq!(contract.know_your_admin(), accounts.alice);
+        assert_eq!(contract.get_poll_info(0).unwrap().audit_id, 7);
+        assert!(contract.set_stablecoin(accounts.django).is_ok());
+        assert_eq!(contract.know_your_stablecoin(), Some(accounts.django));
+        assert!(matches!(
+            contract.set_stablecoin(accounts.bob),
+            Err(voting::Error::StablecoinAlreadySet)
+        ));
+        // written back, the root takes up no more room than the first deployment's.
+        ink::env::set_contract_storage(&root, &contract);
+        assert_eq!(ink::env::contains_contract_storage(&root), Some(68));
     }
 }